@@ -0,0 +1,303 @@
+//! Watching the grid simulations evolve step by step in a terminal.
+//!
+//! Each day that has something to watch turns its input into a sequence of [`Frame`]s,
+//! which a [`Player`] draws with ANSI escape codes, either to a terminal (waiting between
+//! frames) or to a file that [`replay`] can play back later.
+
+use std::{
+    collections::BTreeSet,
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    day11, day13, day20, day9,
+    error::ParseError,
+    grid::{self, Grid},
+};
+
+/// Clears the terminal and moves the cursor to the top left corner. Every rendered frame
+/// starts with it, which is also how recordings are split back into frames.
+pub const CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// One character on screen, with its colour from the 256-colour ANSI palette (`None` for
+/// the terminal's default colour).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<u8>,
+}
+
+impl Cell {
+    pub fn new(glyph: char, colour: u8) -> Self {
+        Cell {
+            glyph,
+            colour: Some(colour),
+        }
+    }
+}
+
+/// One step of a simulation, with a caption shown above it.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// The frame as text, starting with [`CLEAR`], and with escape codes setting colours
+    /// unless `colour` is false.
+    pub fn render(&self, colour: bool) -> String {
+        let mut out = String::with_capacity(
+            CLEAR.len() + self.caption.len() + 2 * self.cells.width() * self.cells.height(),
+        );
+        out.push_str(CLEAR);
+        out.push_str(&self.caption);
+        out.push('\n');
+
+        for row in self.cells.rows() {
+            let mut current = None;
+            for cell in row {
+                if colour && cell.colour != current {
+                    match cell.colour {
+                        Some(c) => out.push_str(&format!("\x1b[38;5;{}m", c)),
+                        None => out.push_str("\x1b[0m"),
+                    }
+                    current = cell.colour;
+                }
+                out.push(cell.glyph);
+            }
+            if current.is_some() {
+                out.push_str("\x1b[0m");
+            }
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/// Draws frames one after the other.
+pub struct Player<W> {
+    out: W,
+    delay: Duration,
+    colour: bool,
+}
+
+impl<W: Write> Player<W> {
+    /// A player waiting `delay` after each frame (use [`Duration::ZERO`] when writing to a
+    /// file).
+    pub fn new(out: W, delay: Duration, colour: bool) -> Self {
+        Player { out, delay, colour }
+    }
+
+    /// Draws all `frames` and returns how many there were.
+    pub fn play(&mut self, frames: impl IntoIterator<Item = Frame>) -> io::Result<usize> {
+        let mut count = 0;
+        for frame in frames {
+            self.out.write_all(frame.render(self.colour).as_bytes())?;
+            self.out.flush()?;
+            thread::sleep(self.delay);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
+/// Plays back frames recorded by a [`Player`], waiting `delay` after each one. Returns how
+/// many frames there were.
+pub fn replay(recording: &str, mut out: impl Write, delay: Duration) -> io::Result<usize> {
+    let mut count = 0;
+    for frame in recording.split(CLEAR).filter(|f| !f.is_empty()) {
+        out.write_all(CLEAR.as_bytes())?;
+        out.write_all(frame.as_bytes())?;
+        out.flush()?;
+        thread::sleep(delay);
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Days that can be animated.
+pub const DAYS: [u8; 4] = [9, 11, 13, 20];
+
+/// The frames of a day's simulation, or `None` if the day has nothing to animate.
+pub fn frames(day: u8, input: &str) -> Result<Option<Box<dyn Iterator<Item = Frame>>>, ParseError> {
+    Ok(Some(match day {
+        9 => Box::new(day9_basins(day9::parse(input)?)),
+        11 => Box::new(day11_octopuses(day11::parse(input)?)),
+        13 => Box::new(day13_folds(day13::parse(input)?)),
+        20 => Box::new(day20_image(day20::parse(input)?)),
+        _ => return Ok(None),
+    }))
+}
+
+/// Basins spreading from their low points, one pass at a time, each in its own colour.
+/// High points are dark.
+fn day9_basins(map: Grid<u8>) -> impl Iterator<Item = Frame> {
+    const PALETTE: [u8; 8] = [196, 208, 226, 46, 51, 33, 129, 201];
+
+    let (mut basin_map, basin_count) = day9::basin_seeds(&map);
+    let mut pass = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        let cells = Grid::from_fn(map.width(), map.height(), |pos| {
+            let glyph = (b'0' + map[pos]) as char;
+            match basin_map[pos] {
+                u32::MAX => Cell::new(glyph, 238),
+                id => Cell::new(glyph, PALETTE[id as usize % PALETTE.len()]),
+            }
+        });
+        let frame = Frame {
+            caption: format!("day 9, {} basins, pass {}", basin_count, pass),
+            cells,
+        };
+
+        done = !day9::grow_basins(&map, &mut basin_map);
+        pass += 1;
+        Some(frame)
+    })
+}
+
+/// Octopus energy levels, from dark to bright, with flashing octopuses in white. Stops
+/// after every octopus flashed at once (or after 1000 steps).
+fn day11_octopuses(mut matrix: Grid<u8>) -> impl Iterator<Item = Frame> {
+    let mut step = 0;
+    let mut flashes = 0;
+    let mut done = false;
+    std::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        let frame = Frame {
+            caption: format!("day 11, step {}, {} flashes", step, flashes),
+            cells: matrix.map(|&level| match level {
+                0 => Cell::new('0', 231),
+                level => Cell::new((b'0' + level) as char, 234 + 2 * level),
+            }),
+        };
+
+        let synced = step > 0 && matrix.iter().all(|&level| level == 0);
+        if synced || step == 1000 {
+            done = true;
+        } else {
+            flashes += day11::step(&mut matrix);
+            step += 1;
+        }
+        Some(frame)
+    })
+}
+
+/// Dots on the transparent paper, before folding and after every fold.
+fn day13_folds((mut dots, folds): day13::Input) -> impl Iterator<Item = Frame> {
+    let draw = |dots: &BTreeSet<(usize, usize)>, caption| {
+        let width = dots.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        let height = dots.iter().map(|&(_, y)| y + 1).max().unwrap_or(0);
+        Frame {
+            caption,
+            cells: Grid::from_fn(width, height, |(y, x)| {
+                if dots.contains(&(x, y)) {
+                    Cell::new('#', 45)
+                } else {
+                    Cell {
+                        glyph: ' ',
+                        colour: None,
+                    }
+                }
+            }),
+        }
+    };
+
+    let first = draw(&dots, format!("day 13, {} dots", dots.len()));
+    std::iter::once(first).chain(folds.into_iter().enumerate().map(move |(idx, fold)| {
+        day13::fold_along(&mut dots, fold);
+        draw(
+            &dots,
+            format!("day 13, fold {} ({:?}), {} dots", idx + 1, fold, dots.len()),
+        )
+    }))
+}
+
+/// The image through 50 enhancements, lit pixels in yellow.
+fn day20_image((mut image, algo_map): (day20::Image, Vec<bool>)) -> impl Iterator<Item = Frame> {
+    const STEPS: usize = 50;
+
+    // every enhancement grows the image by one pixel on each side
+    let (xs, ys) = grid::bounding_box(image.0.iter().copied()).unwrap_or((0..=0, 0..=0));
+    let margin = STEPS as isize + 1;
+    let (min_x, max_x) = (xs.start() - margin, xs.end() + margin);
+    let (min_y, max_y) = (ys.start() - margin, ys.end() + margin);
+
+    (0..=STEPS).map(move |step| {
+        if step > 0 {
+            image = day20::enhance(std::mem::take(&mut image), &algo_map);
+        }
+
+        let (pixels, inverted) = &image;
+        let lit_count = if *inverted {
+            "infinitely many".to_string()
+        } else {
+            pixels.len().to_string()
+        };
+        Frame {
+            caption: format!("day 20, step {}, {} lit pixels", step, lit_count),
+            cells: Grid::from_fn(
+                (max_y - min_y + 1) as usize,
+                (max_x - min_x + 1) as usize,
+                |(row, col)| {
+                    let pos = (min_x + row as isize, min_y + col as isize);
+                    if pixels.contains(&pos) != *inverted {
+                        Cell::new('#', 226)
+                    } else {
+                        Cell::new('.', 236)
+                    }
+                },
+            ),
+        }
+    })
+}
+
+#[test]
+fn render_colours() {
+    let frame = Frame {
+        caption: "test".to_string(),
+        cells: Grid::from_rows([vec![
+            Cell::new('#', 1),
+            Cell::new('#', 1),
+            Cell {
+                glyph: ' ',
+                colour: None,
+            },
+            Cell::new('.', 2),
+        ]])
+        .unwrap(),
+    };
+
+    assert_eq!(frame.render(false), format!("{}test\n## .\n", CLEAR));
+    assert_eq!(
+        frame.render(true),
+        format!(
+            "{}test\n\x1b[38;5;1m##\x1b[0m \x1b[38;5;2m.\x1b[0m\n",
+            CLEAR
+        )
+    );
+
+    let mut recording = Vec::new();
+    Player::new(&mut recording, Duration::ZERO, false)
+        .play([frame.clone(), frame])
+        .unwrap();
+    let mut replayed = Vec::new();
+    let count = replay(
+        std::str::from_utf8(&recording).unwrap(),
+        &mut replayed,
+        Duration::ZERO,
+    )
+    .unwrap();
+    assert_eq!((count, replayed), (2, recording));
+}
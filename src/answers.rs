@@ -0,0 +1,156 @@
+//! Accepted answers, kept in a local `answers.toml` to catch regressions.
+//!
+//! The file has one table per day and one key per part:
+//!
+//! ```toml
+//! [day1]
+//! part1 = 1233
+//! part2 = 1275
+//!
+//! [day13]
+//! part2 = """
+//! #### ...
+//! """
+//! ```
+//!
+//! Answers are compared as they print, so numbers too big for TOML integers can be stored
+//! as strings.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
+
+use toml::{value::Table, Value};
+
+use crate::solver::{Answer, Part};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), String>,
+}
+
+/// How a computed answer compares to the accepted one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// No answer was accepted for this part yet.
+    Unknown,
+}
+
+impl Answers {
+    /// Reads the contents of an `answers.toml` file.
+    pub fn parse(toml: &str) -> Result<Self, String> {
+        let table: Table = toml::from_str(toml).map_err(|e| e.to_string())?;
+
+        let mut answers = Answers::default();
+        for (key, parts) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .ok_or_else(|| format!("expected a table like `[day1]`, found `[{}]`", key))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("expected `[{}]` to be a table", key))?;
+
+            for (key, answer) in parts {
+                let part = key
+                    .strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .and_then(Part::from_number)
+                    .ok_or_else(|| {
+                        format!(
+                            "expected `part1` or `part2` in day {}, found `{}`",
+                            day, key
+                        )
+                    })?;
+                let answer = match answer {
+                    Value::Integer(n) => n.to_string(),
+                    Value::String(s) => s.clone(),
+                    _ => {
+                        return Err(format!(
+                            "expected a number or a string for day {} part {}",
+                            day, part
+                        ))
+                    }
+                };
+                answers.answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Accepts `answer`, replacing whatever was accepted before.
+    pub fn record(&mut self, day: u8, part: Part, answer: &Answer) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    pub fn verify(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer.to_string() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+/// The `answers.toml` file, days in order.
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut last_day = None;
+        for (&(day, part), answer) in &self.answers {
+            if last_day != Some(day) {
+                if last_day.is_some() {
+                    writeln!(f)?;
+                }
+                writeln!(f, "[day{}]", day)?;
+                last_day = Some(day);
+            }
+
+            let value = match answer.parse::<i64>() {
+                Ok(n) => Value::Integer(n),
+                Err(_) => Value::String(answer.clone()),
+            };
+            writeln!(f, "part{} = {}", part, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn round_trip() {
+    let mut answers = Answers::default();
+    answers.record(13, Part::Two, &Answer::Text("# #\n #\"\n".to_string()));
+    answers.record(1, Part::One, &Answer::Number(7));
+    answers.record(22, Part::Two, &Answer::Number(i64::MAX as i128 + 1));
+
+    let toml = answers.to_string();
+    assert!(toml.starts_with("[day1]\npart1 = 7\n\n[day13]\n"));
+    assert_eq!(Answers::parse(&toml), Ok(answers.clone()));
+
+    assert_eq!(
+        answers.verify(1, Part::One, &Answer::Number(7)),
+        Verdict::Correct
+    );
+    assert_eq!(
+        answers.verify(1, Part::One, &Answer::Number(8)),
+        Verdict::Wrong {
+            expected: "7".to_string()
+        }
+    );
+    assert_eq!(
+        answers.verify(1, Part::Two, &Answer::Number(5)),
+        Verdict::Unknown
+    );
+    assert!(Answers::parse("[dayone]\npart1 = 1\n").is_err());
+}
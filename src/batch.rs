@@ -0,0 +1,122 @@
+//! Solving many puzzle inputs at once, in parallel.
+
+use std::{
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::solver::{self, Answer, Part, Solver};
+
+/// Answers to every part a solver supports, and how long parsing and solving took.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solved {
+    pub answers: Vec<(Part, Answer)>,
+    pub time: Duration,
+}
+
+/// One input file given to one solver.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub day: u8,
+    pub input: PathBuf,
+    /// Why the input couldn't be read, parsed or solved.
+    pub outcome: Result<Solved, String>,
+}
+
+/// Parses `input` and solves every part `solver` supports, failing if the input is wrong
+/// or a part has no answer for it.
+pub fn solve(solver: &Solver, input: &str) -> Result<Solved, String> {
+    let start = Instant::now();
+    let parsed = solver.parse(input).map_err(|e| e.to_string())?;
+    let answers = solver
+        .parts()
+        .iter()
+        .map(|&part| match parsed.solve(part).unwrap() {
+            Ok(answer) => Ok((part, answer)),
+            Err(e) => Err(format!("part {}: {}", part, e)),
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Solved {
+        answers,
+        time: start.elapsed(),
+    })
+}
+
+/// Reads the file at `path` and solves it with `solver`. A solver panicking (on an input
+/// it can't handle) fails the run instead of the whole program.
+pub fn run(solver: &Solver, path: &Path) -> Run {
+    let outcome = fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))
+        .and_then(|input| {
+            panic::catch_unwind(AssertUnwindSafe(|| solve(solver, &input))).map_err(|panic| {
+                let msg = panic
+                    .downcast_ref::<&str>()
+                    .copied()
+                    .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                    .unwrap_or("?");
+                format!("solver panicked: {}", msg)
+            })?
+        });
+
+    Run {
+        day: solver.day,
+        input: path.to_owned(),
+        outcome,
+    }
+}
+
+/// The input file of `day` in `dir`: `dir/dayN.txt`.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}.txt", day))
+}
+
+/// Runs every day's default solver on its input file in `dir`, all in parallel. Days
+/// without an input file are left out; the runs are sorted by day.
+pub fn run_all(dir: &Path) -> Vec<Run> {
+    solver::solvers()
+        .into_par_iter()
+        .filter(|s| s.variant == "default")
+        .map(|s| (input_path(dir, s.day), s))
+        .filter(|(path, _)| path.exists())
+        .map(|(path, s)| run(&s, &path))
+        .collect()
+}
+
+/// Runs `solver` on every file in `dir` (not in its subdirectories), all in parallel. The
+/// runs are sorted by file name.
+pub fn run_dir(solver: &Solver, dir: &Path) -> io::Result<Vec<Run>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+
+    Ok(paths.par_iter().map(|path| run(solver, path)).collect())
+}
+
+#[test]
+fn runs() {
+    let day1 = solver::find(1, "default").unwrap();
+    let solved = solve(&day1, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n").unwrap();
+    assert_eq!(
+        solved.answers,
+        [
+            (Part::One, Answer::Number(7)),
+            (Part::Two, Answer::Number(5))
+        ]
+    );
+    assert!(solve(&day1, "deep\n").is_err());
+
+    let missing = run(&day1, &input_path(Path::new("no such dir"), 1));
+    assert!(missing.outcome.unwrap_err().starts_with("failed to read"));
+    assert!(run_all(Path::new("no such dir")).is_empty());
+    assert!(run_dir(&day1, Path::new("no such dir")).is_err());
+}
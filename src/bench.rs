@@ -0,0 +1,136 @@
+//! Timing solvers over several runs, with parsing and every part measured separately.
+
+use std::{
+    fmt::{self, Display},
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::ParseError,
+    solver::{Part, Solver},
+};
+
+/// What gets timed: parsing the input, or solving one part from the parsed input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(part) => f.pad(&format!("part {}", part)),
+        }
+    }
+}
+
+/// Wall time of one phase across all runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if there are no samples.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!(
+            "{:.1?} / {:.1?} / {:.1?}",
+            self.min, self.median, self.max
+        ))
+    }
+}
+
+/// Timings of one solver, phase by phase in the order they run.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub day: u8,
+    pub variant: &'static str,
+    pub runs: usize,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+impl Report {
+    pub fn phase(&self, phase: Phase) -> Option<Stats> {
+        self.phases
+            .iter()
+            .find(|(p, _)| *p == phase)
+            .map(|&(_, stats)| stats)
+    }
+}
+
+/// Runs `solver` on `input` `runs` times (at least once): each run parses the input again,
+/// then solves every part in `parts` from it.
+pub fn bench(
+    solver: &Solver,
+    input: &str,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Report, ParseError> {
+    let runs = runs.max(1);
+    let mut parse_samples = Vec::with_capacity(runs);
+    let mut part_samples = vec![Vec::with_capacity(runs); parts.len()];
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let parsed = solver.parse(black_box(input))?;
+        parse_samples.push(start.elapsed());
+
+        for (&part, samples) in parts.iter().zip(&mut part_samples) {
+            let start = Instant::now();
+            black_box(parsed.solve(part));
+            samples.push(start.elapsed());
+        }
+    }
+
+    let phases = std::iter::once((Phase::Parse, Stats::from_samples(parse_samples)))
+        .chain(
+            parts
+                .iter()
+                .zip(part_samples)
+                .map(|(&part, samples)| (Phase::Part(part), Stats::from_samples(samples))),
+        )
+        .collect();
+
+    Ok(Report {
+        day: solver.day,
+        variant: solver.variant,
+        runs,
+        phases,
+    })
+}
+
+#[test]
+fn median() {
+    let ms =
+        |ms: &[u64]| Stats::from_samples(ms.iter().map(|&n| Duration::from_millis(n)).collect());
+
+    let stats = ms(&[5, 1, 3]);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.max, Duration::from_millis(5));
+
+    assert_eq!(ms(&[4, 1, 2, 8]).median, Duration::from_millis(3));
+}
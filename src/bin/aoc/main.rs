@@ -0,0 +1,696 @@
+use std::{
+    fs,
+    io::{self, BufWriter},
+    panic,
+    path::PathBuf,
+    process,
+    time::{Duration, Instant},
+};
+
+use aoc2021::{
+    animate::{self, Player},
+    answers::{Answers, Verdict},
+    batch::{self, input_path},
+    bench::{self, Phase},
+    netpbm,
+    output::{Format, Output},
+    solver::{self, Part},
+    trace,
+};
+use clap::{Args, Parser, Subcommand};
+
+/// Advent of Code 2021 solutions.
+#[derive(Parser)]
+#[clap(name = "aoc")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle, or every day's with `--all`
+    Run(RunArgs),
+    /// Solve a day's puzzle for every input file in a directory
+    Batch(BatchArgs),
+    /// Time parsing and each part of a day's solvers over several runs
+    Bench(BenchArgs),
+    /// Write a random puzzle input
+    Generate(GenerateArgs),
+    /// Watch a day's simulation step by step (days 9, 11, 13 and 20)
+    Animate(AnimateArgs),
+    /// Play back frames recorded with `aoc animate --output`
+    Replay(ReplayArgs),
+    /// Save a picture of a day's result as a Netpbm image (days 5, 9, 13, 15 and 20)
+    Image(ImageArgs),
+    /// Check answers against the accepted ones in a local answers file
+    Verify(VerifyArgs),
+    /// List every registered solver and its variants
+    List,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to solve
+    #[clap(short, long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Part to solve (all parts the variant supports when omitted)
+    #[clap(short, long, possible_values = ["1", "2"])]
+    part: Option<u8>,
+
+    /// Solver variant, see `aoc list`
+    #[clap(short, long, default_value = "default")]
+    variant: String,
+
+    /// Solve every day with an input file in `--inputs`, in parallel, and print a summary
+    #[clap(long, conflicts_with_all = &["day", "part", "variant", "input", "trace"])]
+    all: bool,
+
+    /// Directory with the puzzle inputs for `--all`, named `day1.txt`, `day2.txt`...
+    #[clap(long, default_value = "inputs")]
+    inputs: PathBuf,
+
+    /// Puzzle input file (standard input when omitted or `-`)
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// Print the solver's debug events on standard error (only in builds with the `trace`
+    /// feature; `AOC_TRACE=11,12` does the same for any program)
+    #[clap(long)]
+    trace: bool,
+
+    #[clap(flatten)]
+    output: Output,
+}
+
+#[derive(Args)]
+struct BatchArgs {
+    /// Day to solve
+    #[clap(short, long)]
+    day: u8,
+
+    /// Solver variant, see `aoc list`
+    #[clap(short, long, default_value = "default")]
+    variant: String,
+
+    /// Directory with the puzzle inputs, every file in it is solved
+    inputs: PathBuf,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to benchmark
+    #[clap(short, long)]
+    day: u8,
+
+    /// Part to time (all parts each variant supports when omitted)
+    #[clap(short, long, possible_values = ["1", "2"])]
+    part: Option<u8>,
+
+    /// Solver variants to compare, can be repeated (every variant of the day when omitted)
+    #[clap(short, long, multiple_occurrences = true)]
+    variant: Vec<String>,
+
+    /// How many times to run each variant
+    #[clap(short = 'n', long, default_value = "10")]
+    runs: usize,
+
+    /// Puzzle input file (standard input when omitted or `-`)
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to generate an input for
+    #[clap(short, long)]
+    day: u8,
+
+    /// How big the input should be: number of lines, side of a grid... depending on the day
+    #[clap(short, long)]
+    size: usize,
+
+    /// Seed of the random number generator, the same seed always gives the same input
+    #[clap(long, default_value = "0")]
+    seed: u64,
+
+    /// File to write the input to (standard output when omitted)
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct AnimateArgs {
+    /// Day to animate
+    #[clap(short, long)]
+    day: u8,
+
+    /// Puzzle input file (standard input when omitted or `-`)
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// Milliseconds to wait between frames
+    #[clap(long, default_value = "100")]
+    delay: u64,
+
+    /// Draw without colours
+    #[clap(long)]
+    no_colour: bool,
+
+    /// File to record the frames to, instead of showing them
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ReplayArgs {
+    /// File recorded with `aoc animate --output`
+    recording: PathBuf,
+
+    /// Milliseconds to wait between frames
+    #[clap(long, default_value = "100")]
+    delay: u64,
+}
+
+#[derive(Args)]
+struct ImageArgs {
+    /// Day to draw
+    #[clap(short, long)]
+    day: u8,
+
+    /// Puzzle input file (standard input when omitted or `-`)
+    #[clap(short, long)]
+    input: Option<PathBuf>,
+
+    /// File to write the image to (standard output when omitted); the format (PBM, PGM or
+    /// PPM) depends on the day, not on the file's extension
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Directory with the puzzle inputs, named `day1.txt`, `day2.txt`...
+    #[clap(long, default_value = "inputs")]
+    inputs: PathBuf,
+
+    /// File with the accepted answers
+    #[clap(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Day to check, can be repeated (every day with an input file when omitted)
+    #[clap(short, long, multiple_occurrences = true)]
+    day: Vec<u8>,
+
+    /// Solver variant, see `aoc list`
+    #[clap(short, long, default_value = "default")]
+    variant: String,
+
+    /// Accept the computed answers, writing them to the answers file
+    #[clap(long)]
+    record: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let res = match cli.command {
+        Command::Run(args) => run(args),
+        Command::Batch(args) => batch(args),
+        Command::Bench(args) => bench(args),
+        Command::Generate(args) => generate(args),
+        Command::Animate(args) => animate(args),
+        Command::Replay(args) => replay(args),
+        Command::Image(args) => image(args),
+        Command::Verify(args) => verify(args),
+        Command::List => {
+            list();
+            Ok(())
+        }
+    };
+
+    if let Err(e) = res {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(args: RunArgs) -> Result<(), String> {
+    let day = match args.day {
+        Some(day) if !args.all => day,
+        _ => return run_all(args),
+    };
+    let solver = solver::find(day, &args.variant)
+        .ok_or_else(|| format!("no solver for day {} with variant {:?}", day, args.variant))?;
+
+    let parts = match args.part.and_then(Part::from_number) {
+        Some(part) if solver.parts().contains(&part) => vec![part],
+        Some(part) => {
+            return Err(format!(
+                "day {} ({}) can't solve part {}",
+                solver.day, solver.variant, part
+            ))
+        }
+        None => solver.parts().to_vec(),
+    };
+
+    let input = read_input(args.input.as_ref())
+        .map_err(|e| format!("failed to read puzzle input: {}", e))?;
+
+    if args.trace {
+        if !trace::COMPILED {
+            eprintln!("warning: no debug events in this build, rebuild with `--features trace`");
+        }
+        trace::enable(solver.day);
+    }
+
+    let parsed = solver.parse(&input).map_err(|e| e.to_string())?;
+    for part in parts {
+        let answer = parsed
+            .solve(part)
+            .unwrap()
+            .map_err(|e| format!("day {} part {}: {}", solver.day, part, e))?;
+        args.output.answer(solver.day, part, answer);
+    }
+
+    Ok(())
+}
+
+fn run_all(args: RunArgs) -> Result<(), String> {
+    // panics are reported in the table
+    panic::set_hook(Box::new(|_| {}));
+    let start = Instant::now();
+    let runs = batch::run_all(&args.inputs);
+    let wall = start.elapsed();
+    if runs.is_empty() {
+        return Err(format!(
+            "no puzzle inputs in {} (expected files like day1.txt)",
+            args.inputs.display()
+        ));
+    }
+
+    let mut failed = 0;
+    if args.output.format == Format::Json {
+        for run in &runs {
+            match &run.outcome {
+                Ok(solved) => {
+                    for (part, answer) in &solved.answers {
+                        args.output.answer(run.day, *part, answer.clone());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("day {}: {}", run.day, e);
+                    failed += 1;
+                }
+            }
+        }
+    } else {
+        println!(
+            "{:>3}  {:<20} {:<20} {:>10}",
+            "day", "part 1", "part 2", "time"
+        );
+        for run in &runs {
+            match &run.outcome {
+                Ok(solved) => println!(
+                    "{:>3}  {:<20} {:<20} {:>10}",
+                    run.day,
+                    solved_part(solved, Part::One),
+                    solved_part(solved, Part::Two),
+                    format!("{:.1?}", solved.time)
+                ),
+                Err(e) => {
+                    println!("{:>3}  error: {}", run.day, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let total: Duration = runs
+            .iter()
+            .filter_map(|run| run.outcome.as_ref().ok())
+            .map(|solved| solved.time)
+            .sum();
+        println!(
+            "{} days in {:.1?} ({:.1?} of solving)",
+            runs.len(),
+            wall,
+            total
+        );
+    }
+
+    if failed > 0 {
+        return Err(format!("{} days failed", failed));
+    }
+    Ok(())
+}
+
+fn batch(args: BatchArgs) -> Result<(), String> {
+    let solver = solver::find(args.day, &args.variant).ok_or_else(|| {
+        format!(
+            "no solver for day {} with variant {:?}",
+            args.day, args.variant
+        )
+    })?;
+
+    // panics are reported in the table
+    panic::set_hook(Box::new(|_| {}));
+    let runs = batch::run_dir(&solver, &args.inputs)
+        .map_err(|e| format!("failed to read {}: {}", args.inputs.display(), e))?;
+    if runs.is_empty() {
+        return Err(format!("no files in {}", args.inputs.display()));
+    }
+
+    let width = runs
+        .iter()
+        .map(|run| file_name(run).len())
+        .max()
+        .unwrap_or(0)
+        .max("file".len());
+    println!(
+        "{:<width$}  {:<20} {:<20} {:>10}",
+        "file",
+        "part 1",
+        "part 2",
+        "time",
+        width = width
+    );
+
+    let mut failed = 0;
+    for run in &runs {
+        match &run.outcome {
+            Ok(solved) => println!(
+                "{:<width$}  {:<20} {:<20} {:>10}",
+                file_name(run),
+                solved_part(solved, Part::One),
+                solved_part(solved, Part::Two),
+                format!("{:.1?}", solved.time),
+                width = width
+            ),
+            Err(e) => {
+                println!("{:<width$}  error: {}", file_name(run), e, width = width);
+                failed += 1;
+            }
+        }
+    }
+    println!("{} files, {} failed", runs.len(), failed);
+
+    if failed > 0 {
+        return Err(format!("{} files failed", failed));
+    }
+    Ok(())
+}
+
+fn file_name(run: &batch::Run) -> String {
+    run.input
+        .file_name()
+        .unwrap_or(run.input.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The answer to `part` for the tables, `-` for parts the solver doesn't support.
+fn solved_part(solved: &batch::Solved, part: Part) -> String {
+    solved
+        .answers
+        .iter()
+        .find(|(p, _)| *p == part)
+        .map_or("-".to_string(), |(_, answer)| one_line(&answer.to_string()))
+}
+
+fn bench(args: BenchArgs) -> Result<(), String> {
+    let solvers = if args.variant.is_empty() {
+        solver::solvers()
+            .into_iter()
+            .filter(|s| s.day == args.day)
+            .collect()
+    } else {
+        args.variant
+            .iter()
+            .map(|variant| {
+                solver::find(args.day, variant).ok_or_else(|| {
+                    format!("no solver for day {} with variant {:?}", args.day, variant)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    if solvers.is_empty() {
+        return Err(format!("no solver for day {}", args.day));
+    }
+
+    let input = read_input(args.input.as_ref())
+        .map_err(|e| format!("failed to read puzzle input: {}", e))?;
+
+    let mut reports = Vec::new();
+    for solver in &solvers {
+        let parts = match args.part.and_then(Part::from_number) {
+            Some(part) if solver.parts().contains(&part) => vec![part],
+            Some(_) => vec![],
+            None => solver.parts().to_vec(),
+        };
+
+        let report = bench::bench(solver, &input, &parts, args.runs).map_err(|e| e.to_string())?;
+        reports.push(report);
+    }
+
+    // one column per variant, one row per phase
+    println!(
+        "day {}, {} runs, min / median / max",
+        args.day,
+        args.runs.max(1)
+    );
+    let mut header = format!("{:<8}", "phase");
+    for report in &reports {
+        header += &format!(" {:<32}", report.variant);
+    }
+    println!("{}", header.trim_end());
+
+    let phases = [Phase::Parse, Phase::Part(Part::One), Phase::Part(Part::Two)];
+    for phase in phases {
+        if reports.iter().all(|r| r.phase(phase).is_none()) {
+            continue;
+        }
+
+        let mut row = format!("{:<8}", phase);
+        for report in &reports {
+            match report.phase(phase) {
+                Some(stats) => row += &format!(" {:<32}", stats),
+                None => row += &format!(" {:<32}", "-"),
+            }
+        }
+        println!("{}", row.trim_end());
+    }
+
+    Ok(())
+}
+
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    let input = aoc2021::generate::generate(args.day, args.size, args.seed)
+        .ok_or_else(|| format!("no puzzle for day {}", args.day))?;
+
+    match args.output {
+        Some(path) => fs::write(&path, input)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e)),
+        None => {
+            print!("{}", input);
+            Ok(())
+        }
+    }
+}
+
+fn animate(args: AnimateArgs) -> Result<(), String> {
+    let input = read_input(args.input.as_ref())
+        .map_err(|e| format!("failed to read puzzle input: {}", e))?;
+    let frames = animate::frames(args.day, &input)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| {
+            format!(
+                "nothing to animate for day {}, try one of {:?}",
+                args.day,
+                animate::DAYS
+            )
+        })?;
+
+    let colour = !args.no_colour;
+    let res = match &args.output {
+        Some(path) => fs::File::create(path).and_then(|file| {
+            Player::new(BufWriter::new(file), Duration::ZERO, colour).play(frames)
+        }),
+        None => Player::new(
+            io::stdout().lock(),
+            Duration::from_millis(args.delay),
+            colour,
+        )
+        .play(frames),
+    };
+
+    res.map(drop)
+        .map_err(|e| format!("failed to draw frames: {}", e))
+}
+
+fn replay(args: ReplayArgs) -> Result<(), String> {
+    let recording = fs::read_to_string(&args.recording)
+        .map_err(|e| format!("failed to read {}: {}", args.recording.display(), e))?;
+
+    animate::replay(
+        &recording,
+        io::stdout().lock(),
+        Duration::from_millis(args.delay),
+    )
+    .map(drop)
+    .map_err(|e| format!("failed to draw frames: {}", e))
+}
+
+fn image(args: ImageArgs) -> Result<(), String> {
+    let input = read_input(args.input.as_ref())
+        .map_err(|e| format!("failed to read puzzle input: {}", e))?;
+    let image = netpbm::export(args.day, &input)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| {
+            format!(
+                "no image for day {}, try one of {:?}",
+                args.day,
+                netpbm::DAYS
+            )
+        })?;
+
+    match args.output {
+        Some(path) => fs::write(&path, image.to_string())
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e)),
+        None => {
+            print!("{}", image);
+            Ok(())
+        }
+    }
+}
+
+fn verify(args: VerifyArgs) -> Result<(), String> {
+    let mut answers = match fs::read_to_string(&args.answers) {
+        Ok(toml) => Answers::parse(&toml)
+            .map_err(|e| format!("failed to read {}: {}", args.answers.display(), e))?,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Answers::default(),
+        Err(e) => return Err(format!("failed to read {}: {}", args.answers.display(), e)),
+    };
+
+    let days = if args.day.is_empty() {
+        (1..=25)
+            .filter(|day| input_path(&args.inputs, *day).exists())
+            .collect()
+    } else {
+        args.day.clone()
+    };
+    if days.is_empty() {
+        return Err(format!(
+            "no puzzle inputs in {} (expected files like day1.txt)",
+            args.inputs.display()
+        ));
+    }
+
+    let (mut wrong, mut unknown, mut failed) = (0, 0, 0);
+    for day in days {
+        let solver = match solver::find(day, &args.variant) {
+            Some(solver) => solver,
+            None => {
+                println!(
+                    "day {:>2}         no solver with variant {:?}",
+                    day, args.variant
+                );
+                failed += 1;
+                continue;
+            }
+        };
+
+        let path = input_path(&args.inputs, day);
+        let parsed = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))
+            .and_then(|input| solver.parse(&input).map_err(|e| e.to_string()));
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("day {:>2}         error: {}", day, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        for &part in solver.parts() {
+            let answer = match parsed.solve(part).unwrap() {
+                Ok(answer) => answer,
+                Err(e) => {
+                    println!("day {:>2} part {}  error: {}", day, part, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            let status = match answers.verify(day, part, &answer) {
+                Verdict::Correct => "ok".to_string(),
+                Verdict::Wrong { expected } => {
+                    wrong += 1;
+                    format!("WRONG, expected {}", one_line(&expected))
+                }
+                Verdict::Unknown => {
+                    unknown += 1;
+                    "new".to_string()
+                }
+            };
+            println!(
+                "day {:>2} part {}  {:<16} {}",
+                day,
+                part,
+                one_line(&answer.to_string()),
+                status
+            );
+
+            if args.record {
+                answers.record(day, part, &answer);
+            }
+        }
+    }
+
+    if args.record {
+        fs::write(&args.answers, answers.to_string())
+            .map_err(|e| format!("failed to write {}: {}", args.answers.display(), e))?;
+        println!("recorded answers in {}", args.answers.display());
+    } else if unknown > 0 {
+        println!(
+            "{} answers not accepted yet, use --record to accept them",
+            unknown
+        );
+    }
+
+    if failed > 0 || (wrong > 0 && !args.record) {
+        return Err(format!("{} wrong answers, {} days failed", wrong, failed));
+    }
+    Ok(())
+}
+
+/// Drawings (multi-line answers) are too big for the table.
+fn one_line(answer: &str) -> String {
+    match answer.trim_end().lines().count() {
+        0 | 1 => answer.to_string(),
+        n => format!("<{} lines>", n),
+    }
+}
+
+fn list() {
+    println!("{:>3}  {:<12} {:<6} description", "day", "variant", "parts");
+    for s in solver::solvers() {
+        let parts = s
+            .parts()
+            .iter()
+            .map(Part::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        println!(
+            "{:>3}  {:<12} {:<6} {}",
+            s.day, s.variant, parts, s.description
+        );
+    }
+}
+
+fn read_input(path: Option<&PathBuf>) -> io::Result<String> {
+    match path {
+        Some(path) if path.as_os_str() != "-" => fs::read_to_string(path),
+        _ => aoc2021::read_stdin(),
+    }
+}
@@ -1,23 +1,157 @@
-use std::io;
+use aoc2021::{
+    day1::{self, Fusion, MultiSweep, Profile, Run},
+    input,
+    output::{Format, Output},
+    solver::Part,
+};
+use clap::Parser;
+use std::{error::Error, io};
 
-fn main() -> io::Result<()> {
-    let mut input = String::new();
+/// Counts how many sliding windows of depth measurements have a larger sum than the
+/// previous one, reading the sonar report from standard input one line at a time.
+///
+/// Lines may have several readings, one per sensor, separated by commas or whitespace:
+/// increases are then counted for every sensor and for all of them fused together.
+#[derive(Parser)]
+struct Args {
+    /// Measurements per window (both puzzle parts, windows of 1 and 3, when omitted)
+    #[clap(short, long)]
+    window: Option<usize>,
 
-    let mut prev = i64::MAX;
-    let mut count = 0_usize;
-    while io::stdin().read_line(&mut input)? != 0 {
-        // drop the \n
-        assert_eq!(input.pop(), Some('\n'));
+    /// How to fuse the readings of several sensors into one
+    #[clap(long, arg_enum, default_value = "median")]
+    fuse: Fusion,
 
-        let num: i64 = input.parse().expect("parse error");
-        if num > prev {
-            count += 1;
+    /// Placeholder for a missing reading, which is skipped
+    #[clap(long, default_value = "-")]
+    missing: String,
+
+    /// Print the ascending, descending and flat runs of the report instead of counting
+    /// increases
+    #[clap(long, conflicts_with = "window")]
+    profile: bool,
+
+    /// Largest change between two measurements that still counts as flat, for `--profile`
+    #[clap(long, default_value = "0", requires = "profile")]
+    tolerance: i64,
+
+    #[clap(flatten)]
+    output: Output,
+}
+
+fn main() {
+    aoc2021::run_main(run);
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    if args.window == Some(0) {
+        return Err("windows need at least one measurement".into());
+    }
+
+    let windows = args.window.map_or(vec![1, 3], |window| vec![window]);
+    let new_sweeps = |channels| -> Vec<MultiSweep> {
+        windows
+            .iter()
+            .map(|&window| MultiSweep::new(channels, window, args.fuse))
+            .collect()
+    };
+    // one channel until the first line says otherwise, so that an empty report has answers
+    let mut sweeps = new_sweeps(1);
+    let mut profile = Profile::new(args.tolerance);
+    let mut columns = None;
+
+    for (idx, line) in input::stream_lines(io::stdin().lock()).enumerate() {
+        let readings = match day1::parse_readings(idx + 1, &line?, &args.missing, columns)? {
+            Some(readings) => readings,
+            None => continue,
+        };
+        if columns.is_none() {
+            columns = Some(readings.len());
+            sweeps = new_sweeps(readings.len());
         }
 
-        prev = num;
-        input.clear();
+        if args.profile {
+            if readings.len() > 1 {
+                return Err("--profile needs one reading per line".into());
+            }
+            if let Some(depth) = readings[0] {
+                profile.push(depth);
+            }
+        } else {
+            for sweep in &mut sweeps {
+                sweep.push(&readings);
+            }
+        }
+    }
+
+    if args.profile {
+        print_profile(&args.output, &profile);
+        return Ok(());
     }
 
-    dbg!(count);
+    let columns = columns.unwrap_or(1);
+    for (window, sweep) in windows.into_iter().zip(sweeps) {
+        if columns == 1 {
+            let increases = sweep.increases()[0];
+            match (args.window, window) {
+                (None, 1) => args.output.answer(1, Part::One, increases),
+                (None, _) => args.output.answer(1, Part::Two, increases),
+                (Some(_), _) => print_window(&args.output, window, None, increases),
+            }
+        } else {
+            for (channel, increases) in sweep.increases().into_iter().enumerate() {
+                let channel = (channel + 1).to_string();
+                print_window(&args.output, window, Some(&channel), increases);
+            }
+            let fused = format!("{:?}", args.fuse).to_lowercase();
+            print_window(&args.output, window, Some(&fused), sweep.fused_increases());
+        }
+    }
     Ok(())
 }
+
+/// Prints the increases counted with windows other than the puzzle's, or in one channel
+/// (a column number, or how the channels were fused).
+fn print_window(output: &Output, window: usize, channel: Option<&str>, increases: usize) {
+    match (output.format, channel) {
+        (Format::Plain, None) => println!("day 1 window {}: {}", window, increases),
+        (Format::Plain, Some(channel)) => {
+            println!("day 1 window {} channel {}: {}", window, channel, increases)
+        }
+        (Format::Json, None) => {
+            println!(r#"{{"day":1,"window":{},"answer":{}}}"#, window, increases)
+        }
+        (Format::Json, Some(channel)) => println!(
+            r#"{{"day":1,"window":{},"channel":"{}","answer":{}}}"#,
+            window, channel, increases
+        ),
+    }
+}
+
+/// Prints the runs of the report, then its longest descent and largest jump: the table from
+/// [`Profile`]'s `Display`, or one JSON record for each (`null` when there's none).
+fn print_profile(output: &Output, profile: &Profile) {
+    if output.format == Format::Plain {
+        print!("{}", profile);
+        return;
+    }
+
+    let run_json = |run: &Run| {
+        format!(
+            r#"{{"trend":"{}","start":{},"length":{},"change":{}}}"#,
+            run.trend, run.start, run.len, run.change
+        )
+    };
+    for run in profile.runs() {
+        println!(r#"{{"day":1,"run":{}}}"#, run_json(run));
+    }
+    let descent = profile
+        .longest_descent()
+        .map_or("null".to_string(), run_json);
+    println!(r#"{{"day":1,"longest_descent":{}}}"#, descent);
+    let jump = profile.largest_jump().map_or("null".to_string(), |jump| {
+        format!(r#"{{"at":{},"change":{}}}"#, jump.at, jump.change)
+    });
+    println!(r#"{{"day":1,"largest_jump":{}}}"#, jump);
+}
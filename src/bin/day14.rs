@@ -1,56 +1,15 @@
-use std::{
-    collections::HashMap,
-    io::{self, BufRead},
-};
+use aoc2021::{day14, output::Output, solver::Part};
+use std::error::Error;
 
 fn main() {
-    let stdin = io::stdin();
-    let mut lines = stdin.lock().lines().map(Result::unwrap);
-
-    let initial: Vec<char> = lines.next().unwrap().trim().chars().collect();
-    lines.next().unwrap(); // empty line
-    let rules = lines
-        .filter(|l| !l.trim().is_empty())
-        .map(|line| {
-            dbg!(&line);
-            let (before, after) = line.split_once(" -> ").unwrap();
-
-            let c1 = before.chars().next().unwrap();
-            let c2 = before.chars().nth(1).unwrap();
-
-            ((c1, c2), after.chars().next().unwrap())
-        })
-        .collect::<HashMap<_, _>>();
-
-    let mut polymer = initial;
-    for _ in 0..10 {
-        polymer = step_polymer(polymer, &rules);
-    }
-
-    let frequencies = el_frequencies(&polymer);
-    let (_, &min) = frequencies.iter().min_by_key(|(_, &count)| count).unwrap();
-    let (_, &max) = frequencies.iter().max_by_key(|(_, &count)| count).unwrap();
-
-    dbg!("part1", dbg!(max) - dbg!(min));
-}
-
-fn step_polymer(polymer: Vec<char>, rules: &HashMap<(char, char), char>) -> Vec<char> {
-    polymer
-        .windows(2)
-        .flat_map(|v| match *v {
-            [a, b, ..] => [a, rules[&(a, b)]],
-            _ => unreachable!(),
-        })
-        .chain(std::iter::once(polymer.iter().last().copied().unwrap()))
-        .collect()
+    aoc2021::run_main(run);
 }
 
-fn el_frequencies(polymer: &[char]) -> HashMap<char, usize> {
-    let mut map = HashMap::new();
-
-    for &c in polymer {
-        *map.entry(c).or_default() += 1;
-    }
+fn run() -> Result<(), Box<dyn Error>> {
+    let out = Output::from_args();
+    let input = aoc2021::read_stdin()?;
+    let parsed = day14::parse(&input)?;
 
-    map
+    out.answer(14, Part::One, day14::part1(&parsed));
+    Ok(())
 }
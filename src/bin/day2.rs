@@ -1,61 +1,83 @@
-use std::io::{self, BufRead};
+use aoc2021::{
+    day2::{self, Navigation, Navigator, Trajectory},
+    output::{Format, Output},
+};
+use clap::Parser;
+use std::{
+    error::Error,
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
 
-#[derive(Debug)]
-enum Instr {
-    Forward(i64),
-    Down(i64),
-    Up(i64),
-}
+/// Runs the submarine commands read from standard input (the puzzle input, or a script with
+/// `back`, `repeat N { ... }`, macros and `#` comments), with either way of reading them
+/// (both when omitted).
+#[derive(Parser)]
+struct Args {
+    /// How `up` and `down` move the submarine: `direct` (part 1) changes the depth, `aim`
+    /// (part 2) changes the aim
+    #[clap(short, long, arg_enum)]
+    navigator: Option<Navigation>,
 
-fn parse_instr(s: impl AsRef<str>) -> Instr {
-    let mut tokens = s.as_ref().split(' ');
-    let typ = tokens.next().expect("instruction identifier");
-    match typ {
-        "forward" => {
-            let n = tokens
-                .next()
-                .expect("forward accepts one argument")
-                .parse()
-                .expect("displacement is a positive integer");
-            assert!(n > 0, "displacement is a positive integer");
-            Instr::Forward(n)
-        }
-        "down" => {
-            let n = tokens
-                .next()
-                .expect("forward accepts one argument")
-                .parse()
-                .expect("displacement is a positive integer");
-            assert!(n > 0, "displacement is a positive integer");
-            Instr::Down(n)
-        }
-        "up" => {
-            let n = tokens
-                .next()
-                .expect("forward accepts one argument")
-                .parse()
-                .expect("displacement is a positive integer");
-            assert!(n > 0, "displacement is a positive integer");
-            Instr::Up(n)
-        }
-        _ => unreachable!("unknown instruction type"),
-    }
+    /// CSV file to write the position after every command to, also printing the deepest
+    /// point and the distance travelled
+    #[clap(short, long, requires = "navigator")]
+    trajectory: Option<PathBuf>,
+
+    #[clap(flatten)]
+    output: Output,
 }
 
 fn main() {
-    let (horiz, depth, _aim) = io::stdin()
-        .lock()
-        .lines()
-        .map(|l| l.unwrap())
-        .map(parse_instr)
-        .fold(
-            (0_i64, 0_i64, 0_i64),
-            |(horiz, depth, aim), instr| match instr {
-                Instr::Forward(n) => (horiz + n, depth + aim * n, aim),
-                Instr::Up(n) => (horiz, depth, aim - n),
-                Instr::Down(n) => (horiz, depth, aim + n),
-            },
-        );
+    aoc2021::run_main(run);
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let input = aoc2021::read_stdin()?;
+    let parsed = day2::parse(&input)?;
 
-    dbg!(horiz * depth);
+    let navigators = match args.navigator {
+        Some(navigator) => vec![navigator],
+        None => vec![Navigation::Direct, Navigation::Aim],
+    };
+    for navigator in navigators {
+        let pos = match &args.trajectory {
+            Some(path) => {
+                let trajectory = navigator.trajectory(&parsed);
+                let mut out = BufWriter::new(File::create(path)?);
+                trajectory.write_csv(&mut out)?;
+                out.flush()?;
+                print_trajectory(&args.output, navigator, &trajectory);
+                trajectory.end()
+            }
+            None => navigator.navigate(&parsed),
+        };
+        args.output.debug(|| format!("{:?}: {:?}", navigator, pos));
+        args.output.answer(2, navigator.part(), pos.product());
+    }
+    Ok(())
+}
+
+/// Prints the deepest point of the course and how far the submarine went.
+fn print_trajectory(output: &Output, navigator: Navigation, trajectory: &Trajectory) {
+    let (step, depth) = trajectory.max_depth();
+    let navigator = format!("{:?}", navigator).to_lowercase();
+    match output.format {
+        Format::Plain => println!(
+            "day 2 {}: max depth {} at step {}, distance {:.2}",
+            navigator,
+            depth,
+            step,
+            trajectory.distance()
+        ),
+        Format::Json => println!(
+            r#"{{"day":2,"navigator":"{}","max_depth":{},"step":{},"distance":{}}}"#,
+            navigator,
+            depth,
+            step,
+            trajectory.distance()
+        ),
+    }
 }
@@ -1,70 +1,15 @@
-struct Player {
-    pos: u32,
-    score: u32,
-}
-
-// aged like milk \/
-trait Die {
-    fn roll(&mut self) -> u32;
-}
-
-struct DeterministicDie {
-    state: u32,
-    roll_count: usize,
-}
+use aoc2021::{day21, output::Output, solver::Part};
+use std::error::Error;
 
 fn main() {
-    let mut players = [Player::new(6), Player::new(4)];
-    let mut det_die = DeterministicDie::default();
-
-    let winner_idx = 'outer: loop {
-        for (idx, p) in players.iter_mut().enumerate() {
-            p.play(&mut det_die);
-
-            if p.score >= 1000 {
-                break 'outer idx
-            }
-        }
-    };
-
-    let loser = &players[(winner_idx + 1) % 2];
-    dbg!(loser.score as usize * det_die.roll_count);
+    aoc2021::run_main(run);
 }
 
-impl Player {
-    fn new(starting_pos: u32) -> Self {
-        Player {
-            score: 0,
-            pos: starting_pos,
-        }
-    }
-
-    fn play(&mut self, die: &mut dyn Die) {
-        for _ in 0..3 {
-            self.pos = (self.pos - 1 + die.roll()) % 10 + 1;
-        }
-
-        self.score += self.pos;
-    }
-}
-
-impl Die for DeterministicDie {
-    fn roll(&mut self) -> u32 {
-        let n = self.state;
-        self.state = (self.state + 1) % 100;
-
-        self.roll_count += 1;
-
-        n + 1
-    }
-}
+fn run() -> Result<(), Box<dyn Error>> {
+    let out = Output::from_args();
+    let input = aoc2021::read_stdin()?;
+    let parsed = day21::parse(&input)?;
 
-#[allow(clippy::derivable_impls)]
-impl Default for DeterministicDie {
-    fn default() -> Self {
-        DeterministicDie {
-            state: 0,
-            roll_count: 0,
-        }
-    }
+    out.answer(21, Part::One, day21::part1(&parsed));
+    Ok(())
 }
@@ -1,16 +1,15 @@
-use std::io::{self, BufRead};
+use aoc2021::{day7, output::Output, solver::Part};
+use std::error::Error;
 
 fn main() {
-    let mut positions: Vec<i64> = io::stdin()
-        .lock()
-        .split(b',')
-        .map(Result::unwrap)
-        .map(|input| std::str::from_utf8(&input).unwrap().trim().parse().unwrap())
-        .collect();
+    aoc2021::run_main(run);
+}
 
-    // compute median
-    positions.sort_unstable();
-    let optimal = positions[positions.len().div_euclid(2)];
+fn run() -> Result<(), Box<dyn Error>> {
+    let out = Output::from_args();
+    let input = aoc2021::read_stdin()?;
+    let parsed = day7::parse(&input)?;
 
-    dbg!(positions.iter().map(|&p| (optimal - p).abs()).sum::<i64>());
+    out.answer(7, Part::One, day7::part1(&parsed));
+    Ok(())
 }
@@ -0,0 +1,427 @@
+use std::fmt::{self, Display, Write};
+
+use clap::ArgEnum;
+use rand::Rng;
+
+use crate::error::{ParseError, Source};
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(1, input);
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|l| src.parse(l, "a depth measurement (integer)"))
+        .collect()
+}
+
+/// Counts how many measurements are larger than the previous one.
+pub fn part1(depths: &[i64]) -> usize {
+    count_increases(depths.iter().copied(), 1)
+}
+
+/// Counts how many three-measurement sliding windows are larger than the previous one.
+pub fn part2(depths: &[i64]) -> usize {
+    count_increases(depths.iter().copied(), 3)
+}
+
+/// Counts how many sliding windows of `window` measurements have a larger sum than the
+/// previous one.
+pub fn count_increases(depths: impl IntoIterator<Item = i64>, window: usize) -> usize {
+    let mut sweep = SonarSweep::new(window);
+    for depth in depths {
+        sweep.push(depth);
+    }
+    sweep.increases()
+}
+
+/// Counts window sum increases one measurement at a time, for reports too long to keep in
+/// memory.
+///
+/// Two consecutive windows share all but their first and last measurements, so the newer
+/// one has a larger sum exactly when its last measurement is larger than the older one's
+/// first: only the last `window` measurements are kept, and no sum is ever computed.
+#[derive(Clone, Debug)]
+pub struct SonarSweep<T = i64> {
+    window: usize,
+    /// The last measurements, oldest at `oldest` (once full).
+    last: Vec<T>,
+    oldest: usize,
+    increases: usize,
+}
+
+impl<T: PartialOrd + Copy> SonarSweep<T> {
+    /// Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "windows need at least one measurement");
+        SonarSweep {
+            window,
+            last: Vec::with_capacity(window),
+            oldest: 0,
+            increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: T) {
+        if self.last.len() < self.window {
+            self.last.push(depth);
+            return;
+        }
+
+        if depth > self.last[self.oldest] {
+            self.increases += 1;
+        }
+        self.last[self.oldest] = depth;
+        self.oldest = (self.oldest + 1) % self.window;
+    }
+
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+}
+
+/// Whether the depth goes up, down or stays about the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Ascending,
+    Descending,
+    Flat,
+}
+
+/// Consecutive measurements with the same trend.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Run {
+    pub trend: Trend,
+    /// Index of the first measurement (the last one of the previous run, if any).
+    pub start: usize,
+    /// Number of measurements, at least 2.
+    pub len: usize,
+    /// Last measurement minus first.
+    pub change: i64,
+}
+
+/// The change from one measurement to the next.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Jump {
+    /// Index of the measurement after the jump.
+    pub at: usize,
+    pub change: i64,
+}
+
+/// Splits a depth report into ascending, descending and flat runs, one measurement at a
+/// time.
+///
+/// Changes no larger than the noise tolerance are flat, larger ones go up or down, and a
+/// run ends as soon as the trend changes.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    tolerance: i64,
+    runs: Vec<Run>,
+    largest_jump: Option<Jump>,
+    last: Option<i64>,
+    count: usize,
+}
+
+impl Profile {
+    pub fn new(tolerance: i64) -> Self {
+        Profile {
+            tolerance: tolerance.abs(),
+            runs: Vec::new(),
+            largest_jump: None,
+            last: None,
+            count: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: i64) {
+        let at = self.count;
+        self.count += 1;
+
+        let change = match self.last.replace(depth) {
+            Some(last) => depth - last,
+            None => return,
+        };
+
+        if self
+            .largest_jump
+            .is_none_or(|jump| change.abs() > jump.change.abs())
+        {
+            self.largest_jump = Some(Jump { at, change });
+        }
+
+        let trend = match change {
+            c if c > self.tolerance => Trend::Ascending,
+            c if c < -self.tolerance => Trend::Descending,
+            _ => Trend::Flat,
+        };
+        match self.runs.last_mut() {
+            Some(run) if run.trend == trend => {
+                run.len += 1;
+                run.change += change;
+            }
+            _ => self.runs.push(Run {
+                trend,
+                start: at - 1,
+                len: 2,
+                change,
+            }),
+        }
+    }
+
+    /// Every run so far, in order.
+    pub fn runs(&self) -> &[Run] {
+        &self.runs
+    }
+
+    /// The descending run with the most measurements (the first one in case of a tie).
+    pub fn longest_descent(&self) -> Option<&Run> {
+        self.runs
+            .iter()
+            .filter(|run| run.trend == Trend::Descending)
+            .fold(None, |longest: Option<&Run>, run| match longest {
+                Some(longest) if longest.len >= run.len => Some(longest),
+                _ => Some(run),
+            })
+    }
+
+    /// The largest change between two measurements, up or down (the first one in case of a
+    /// tie).
+    pub fn largest_jump(&self) -> Option<Jump> {
+        self.largest_jump
+    }
+}
+
+/// The report: one line per run, then the longest descent and the largest jump.
+impl Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<10} {:>8} {:>8} {:>8}",
+            "trend", "start", "length", "change"
+        )?;
+        for run in &self.runs {
+            writeln!(
+                f,
+                "{:<10} {:>8} {:>8} {:>+8}",
+                run.trend, run.start, run.len, run.change
+            )?;
+        }
+
+        match self.longest_descent() {
+            Some(run) => writeln!(
+                f,
+                "longest descent: {} measurements from {}, {:+}",
+                run.len, run.start, run.change
+            )?,
+            None => writeln!(f, "longest descent: none")?,
+        }
+        match self.largest_jump {
+            Some(jump) => writeln!(f, "largest jump: {:+} at {}", jump.change, jump.at),
+            None => writeln!(f, "largest jump: none"),
+        }
+    }
+}
+
+impl Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Trend::Ascending => "ascending",
+            Trend::Descending => "descending",
+            Trend::Flat => "flat",
+        })
+    }
+}
+
+/// How to combine the readings of every channel at one index into a single depth.
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fusion {
+    Median,
+    Mean,
+}
+
+impl Fusion {
+    /// The fused depth, `None` without any reading.
+    pub fn fuse(self, readings: impl IntoIterator<Item = i64>) -> Option<f64> {
+        let mut readings: Vec<i64> = readings.into_iter().collect();
+        if readings.is_empty() {
+            return None;
+        }
+
+        let n = readings.len();
+        Some(match self {
+            Fusion::Median => {
+                readings.sort_unstable();
+                (readings[(n - 1) / 2] as f64 + readings[n / 2] as f64) / 2.0
+            }
+            Fusion::Mean => readings.iter().map(|&r| r as f64).sum::<f64>() / n as f64,
+        })
+    }
+}
+
+/// One line of a report with several sensors: one reading per channel, `None` when the
+/// sensor missed it.
+pub type Readings = Vec<Option<i64>>;
+
+/// Counts window sum increases for every channel of a report, and for the channels fused
+/// into one. Missing readings are skipped: windows span over them.
+#[derive(Clone, Debug)]
+pub struct MultiSweep {
+    fusion: Fusion,
+    channels: Vec<SonarSweep>,
+    fused: SonarSweep<f64>,
+}
+
+impl MultiSweep {
+    /// Panics if `window` is 0.
+    pub fn new(channels: usize, window: usize, fusion: Fusion) -> Self {
+        MultiSweep {
+            fusion,
+            channels: vec![SonarSweep::new(window); channels],
+            fused: SonarSweep::new(window),
+        }
+    }
+
+    /// Adds one line of readings, which must have one per channel.
+    pub fn push(&mut self, readings: &[Option<i64>]) {
+        debug_assert_eq!(readings.len(), self.channels.len());
+        for (sweep, &reading) in self.channels.iter_mut().zip(readings) {
+            if let Some(depth) = reading {
+                sweep.push(depth);
+            }
+        }
+        if let Some(depth) = self.fusion.fuse(readings.iter().flatten().copied()) {
+            self.fused.push(depth);
+        }
+    }
+
+    /// Increases counted in each channel, in column order.
+    pub fn increases(&self) -> Vec<usize> {
+        self.channels.iter().map(SonarSweep::increases).collect()
+    }
+
+    pub fn fused_increases(&self) -> usize {
+        self.fused.increases()
+    }
+}
+
+/// Parses line `line_number` (1-based) of a report read one line at a time: readings
+/// separated by commas or whitespace, `missing` for a missing one (as well as empty
+/// fields between commas). `None` for a blank line.
+///
+/// Lines must have `columns` readings, if given.
+pub fn parse_readings(
+    line_number: usize,
+    line: &str,
+    missing: &str,
+    columns: Option<usize>,
+) -> Result<Option<Readings>, ParseError> {
+    let line = line.trim_end();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let src = Source::new(1, line);
+    let at_line = |e: ParseError| ParseError {
+        line: line_number,
+        ..e
+    };
+
+    let fields: Vec<&str> = if line.contains(',') {
+        line.split(',').map(str::trim).collect()
+    } else {
+        line.split_whitespace().collect()
+    };
+    if let Some(columns) = columns.filter(|&c| c != fields.len()) {
+        let expected = format!("{} readings per line, like the first one", columns);
+        return Err(at_line(src.error(line, expected)));
+    }
+
+    fields
+        .into_iter()
+        .map(|field| match field {
+            "" => Ok(None),
+            f if f == missing => Ok(None),
+            f => src.parse(f, "a depth measurement (integer)").map(Some),
+        })
+        .collect::<Result<_, _>>()
+        .map(Some)
+        .map_err(at_line)
+}
+
+/// Random sonar report with `size` depth measurements, mostly getting deeper.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut depth: i64 = rng.gen_range(100..200);
+    let mut out = String::new();
+    for _ in 0..size.max(1) {
+        depth = (depth + rng.gen_range(-20..=30)).max(0);
+        writeln!(out, "{}", depth).unwrap();
+    }
+    out
+}
+
+#[test]
+fn windows() {
+    let depths = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    assert_eq!(count_increases(depths, 2), 5);
+    assert_eq!(count_increases(depths, 10), 0);
+    assert_eq!(count_increases([], 3), 0);
+}
+
+#[test]
+fn profile_runs() {
+    use Trend::*;
+
+    let mut profile = Profile::new(2);
+    for depth in [199, 200, 208, 210, 200, 207, 240, 269, 260, 263] {
+        profile.push(depth);
+    }
+    let trends: Vec<_> = profile.runs().iter().map(|run| run.trend).collect();
+    assert_eq!(
+        trends,
+        [Flat, Ascending, Flat, Descending, Ascending, Descending, Ascending]
+    );
+    assert_eq!(
+        profile.runs()[4],
+        Run {
+            trend: Ascending,
+            start: 4,
+            len: 4,
+            change: 69
+        }
+    );
+    assert_eq!(profile.longest_descent().unwrap().start, 3);
+    assert_eq!(profile.largest_jump(), Some(Jump { at: 6, change: 33 }));
+
+    let flat = Profile::new(0);
+    assert_eq!(flat.runs(), []);
+    assert_eq!(flat.largest_jump(), None);
+}
+
+#[test]
+fn multi_channel() {
+    // three sensors, with missing readings
+    let report = ["1, 5,3", "2,-,4", "3,4,", "2\t6  9"];
+    let readings: Vec<_> = report
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| parse_readings(idx + 1, line, "-", Some(3)).unwrap())
+        .collect();
+    assert_eq!(readings[1], Some(vec![Some(2), None, Some(4)]));
+    assert_eq!(readings[2], Some(vec![Some(3), Some(4), None]));
+
+    let mut median = MultiSweep::new(3, 1, Fusion::Median);
+    let mut mean = MultiSweep::new(3, 2, Fusion::Mean);
+    for line in readings.iter().flatten() {
+        median.push(line);
+        mean.push(line);
+    }
+    assert_eq!(median.increases(), [2, 1, 2]);
+    assert_eq!(median.fused_increases(), 2);
+    assert_eq!(mean.fused_increases(), 2);
+
+    let e = parse_readings(7, "1,2", "-", Some(3)).unwrap_err();
+    assert_eq!((e.line, e.column), (7, 1));
+    assert_eq!(parse_readings(8, "  ", "-", None), Ok(None));
+}
@@ -0,0 +1,172 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::error::{ParseError, Source};
+
+pub fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(10, input);
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            match line.find(|c| !matches!(c, '(' | ')' | '[' | ']' | '{' | '}' | '<' | '>')) {
+                Some(idx) => Err(src.error(&line[idx..], "one of `()[]{}<>`")),
+                None => Ok(line.to_owned()),
+            }
+        })
+        .collect()
+}
+
+/// Total syntax error score of the corrupted lines.
+pub fn part1(input: &[String]) -> u64 {
+    input
+        .iter()
+        .filter_map(|chunk| find_incorrect_closing(chunk))
+        .map(wrong_char_score)
+        .sum::<u64>()
+}
+
+/// Middle completion score of the incomplete lines.
+pub fn part2(input: &[String]) -> u64 {
+    let mut scores: Vec<_> = input
+        .iter()
+        .filter(|chunk| find_incorrect_closing(chunk).is_none())
+        .map(|chunk| compute_completion_seq(chunk))
+        .map(completion_seq_score)
+        .collect();
+    scores.sort_unstable();
+
+    scores[scores.len() / 2]
+}
+
+fn find_incorrect_closing(chunk: &str) -> Option<char> {
+    let mut stack = Vec::new();
+    for c in chunk.chars() {
+        match c {
+            '{' | '[' | '(' | '<' => stack.push(c),
+            '}' => {
+                if stack.pop() != Some('{') {
+                    return Some(c);
+                }
+            }
+            ']' => {
+                if stack.pop() != Some('[') {
+                    return Some(c);
+                }
+            }
+            ')' => {
+                if stack.pop() != Some('(') {
+                    return Some(c);
+                }
+            }
+            '>' => {
+                if stack.pop() != Some('<') {
+                    return Some(c);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    None
+}
+
+fn compute_completion_seq(chunk: &str) -> String {
+    let mut stack = Vec::new();
+    for c in chunk.chars() {
+        match c {
+            '}' | ']' | ')' | '>' => assert_eq!(stack.pop(), Some(c)),
+            '{' => stack.push('}'),
+            '[' => stack.push(']'),
+            '(' => stack.push(')'),
+            '<' => stack.push('>'),
+            _ => unreachable!(),
+        }
+    }
+
+    stack.into_iter().rev().collect()
+}
+
+fn wrong_char_score(c: char) -> u64 {
+    match c {
+        ')' => 3,
+        ']' => 57,
+        '}' => 1197,
+        '>' => 25137,
+        _ => unreachable!(),
+    }
+}
+
+fn completion_seq_score(seq: String) -> u64 {
+    seq.chars()
+        .map(|c| match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            _ => unreachable!(),
+        })
+        .fold(0, |acc, x| acc * 5 + x)
+}
+
+/// Random navigation subsystem with `size` lines, each one either corrupted or incomplete.
+/// There is always an odd number of incomplete lines, so part 2 has a middle score.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut incomplete: Vec<bool> = (0..size).map(|_| rng.gen()).collect();
+    if incomplete.iter().filter(|&&i| i).count() % 2 == 0 {
+        let idx = rng.gen_range(0..size);
+        incomplete[idx] = !incomplete[idx];
+    }
+
+    let mut out = String::new();
+    for incomplete in incomplete {
+        let (mut line, stack) = generate_chunks(rng);
+        if !incomplete {
+            // close the wrong chunk, then keep going for a bit
+            let expected = closing(*stack.last().unwrap());
+            let wrong = loop {
+                let c = *[')', ']', '}', '>'].choose(rng).unwrap();
+                if c != expected {
+                    break c;
+                }
+            };
+            line.push(wrong);
+            line.push_str(&generate_chunks(rng).0);
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+/// Random chunks that leave at least one of them open, and the stack of open chunks.
+fn generate_chunks(rng: &mut impl Rng) -> (String, Vec<char>) {
+    let mut line = String::new();
+    let mut stack = Vec::new();
+    for _ in 0..rng.gen_range(10..60) {
+        if stack.is_empty() || rng.gen_bool(0.55) {
+            let open = *['(', '[', '{', '<'].choose(rng).unwrap();
+            stack.push(open);
+            line.push(open);
+        } else {
+            line.push(closing(stack.pop().unwrap()));
+        }
+    }
+    if stack.is_empty() {
+        stack.push('(');
+        line.push('(');
+    }
+    (line, stack)
+}
+
+fn closing(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,132 @@
+use rand::Rng;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+    solver::NoAnswer,
+};
+
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    Grid::parse_digits(&Source::new(11, input), input, 0..=9, "energy level")
+}
+
+/// Number of flashes after 100 steps.
+pub fn part1(matrix: &Grid<u8>) -> usize {
+    let mut m1 = matrix.clone();
+    let mut flash_count = 0_usize;
+    for _ in 0..100 {
+        flash_count += step(&mut m1);
+    }
+
+    flash_count
+}
+
+/// Steps part 2 waits for every octopus to flash at once (the puzzle's grids take a few
+/// hundred), as some grids never do.
+pub const MAX_STEPS: usize = 10_000;
+
+/// First step during which all octopuses flash.
+pub fn part2(matrix: &Grid<u8>) -> Result<usize, NoAnswer> {
+    let mut matrix = matrix.clone();
+    for i in 1..=MAX_STEPS {
+        step(&mut matrix);
+
+        if matrix.iter().all(|&level| level == 0) {
+            return Ok(i);
+        }
+    }
+
+    Err(NoAnswer(format!(
+        "the octopuses don't all flash at once within {} steps",
+        MAX_STEPS
+    )))
+}
+
+pub fn step(matrix: &mut Grid<u8>) -> usize {
+    increase_energy_levels_for_all_by_one(matrix);
+
+    // Redo flash updates until no more octopuses are flashing
+    let mut flash_count = 0_usize;
+    let mut partial_flash_count = 1_usize;
+    while partial_flash_count > 0 {
+        partial_flash_count = flash_update(matrix);
+        flash_count += partial_flash_count;
+    }
+
+    reset_flashed_octopuses(matrix);
+    flash_count
+}
+
+fn increase_energy_levels_for_all_by_one(matrix: &mut Grid<u8>) {
+    for energy_level in matrix.iter_mut() {
+        *energy_level += 1;
+    }
+}
+
+fn flash_update(matrix: &mut Grid<u8>) -> usize {
+    let mut flash_count = 0_usize;
+
+    // octopuses marked with 9 are about to flash, marked with 11 means they already flashed in this round
+    for pos in matrix.positions() {
+        if matrix[pos] == 10 {
+            crate::trace!(11, "flash", pos = pos);
+            matrix[pos] = 11;
+            flash_count += 1;
+
+            // increment adjacent, without marking them as flashed
+            for adj in matrix.neighbours8(pos) {
+                crate::trace!(11, "charge", pos = adj, from = pos);
+                if matrix[adj] < 10 {
+                    matrix[adj] += 1;
+                }
+            }
+        }
+    }
+
+    flash_count
+}
+
+fn reset_flashed_octopuses(matrix: &mut Grid<u8>) {
+    for energy_level in matrix.iter_mut() {
+        if *energy_level == 11 {
+            *energy_level = 0;
+        }
+    }
+}
+
+/// Random `size`x`size` grid of energy levels (the puzzle's grids are 10x10) whose
+/// octopuses all flash at once within 1000 steps, so that part 2 has an answer.
+///
+/// Random grids do that less and less often as they get bigger, but grids with closer
+/// energy levels do it sooner: the levels are drawn from a narrower range after every 20
+/// failed attempts, down to a single level, where every octopus flashes at once from the
+/// start.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    for max_level in (0..=9).rev() {
+        for _ in 0..20 {
+            let input = crate::day9::digit_grid(rng, size, |rng| rng.gen_range(0..=max_level));
+
+            let mut matrix = parse(&input).unwrap();
+            if (0..1000).any(|_| step(&mut matrix) == size * size) {
+                return input;
+            }
+        }
+    }
+    unreachable!("grids with a single energy level all flash at once")
+}
+
+#[test]
+fn synchronisation() {
+    let never = Grid::parse_digits(&Source::new(11, "09\n"), "09\n", 0..=9, "").unwrap();
+    assert!(part2(&never).is_err());
+
+    use rand::SeedableRng;
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for size in [1, 10, 20] {
+        let grid = parse(&generate(&mut rng, size)).unwrap();
+        assert!(part2(&grid).unwrap() <= 1000, "size {}", size);
+    }
+}
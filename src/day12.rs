@@ -0,0 +1,284 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{ParseError, Source},
+    search,
+};
+
+pub fn parse(input: &str) -> Result<Graph, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(12, input);
+    let mut graph = Graph::default();
+
+    for line in input.lines().filter(|l| !l.trim().is_empty()) {
+        let line = line.trim();
+        let (edge_from, edge_to) =
+            src.require(line.split_once('-'), line, "a connection like `A-b`")?;
+
+        for cave in [edge_from, edge_to] {
+            if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(src.error(cave, "a cave name (letters only)"));
+            }
+        }
+        if is_big(edge_from) && is_big(edge_to) {
+            // there would be infinitely many paths going back and forth between them
+            return Err(src.error(line, "no connections between two big caves"));
+        }
+
+        if edge_from != "end" && edge_to != "start" {
+            graph.connect(edge_from, edge_to);
+        }
+        if edge_to != "end" && edge_from != "start" {
+            graph.connect(edge_to, edge_from);
+        }
+    }
+
+    for cave in ["start", "end"] {
+        if !graph.nodes.contains_key(cave) {
+            return Err(src.error_at_end(format!("a connection to the `{}` cave", cave)));
+        }
+    }
+    if graph.nodes.len() > MAX_CAVES {
+        return Err(src.error_at_end(format!("at most {} caves", MAX_CAVES)));
+    }
+
+    graph.shrink_to_fit();
+    Ok(graph)
+}
+
+/// Caves are tracked with one bit each in a `u128`.
+const MAX_CAVES: usize = 128;
+
+fn is_big(cave: &str) -> bool {
+    cave.chars().next().unwrap().is_uppercase()
+}
+
+/// Number of paths that visit small caves at most once.
+pub fn part1(graph: &Graph) -> usize {
+    graph.count_paths("start", "end", false)
+}
+
+/// Number of paths that may visit a single small cave twice.
+pub fn part2(graph: &Graph) -> usize {
+    graph.count_paths("start", "end", true)
+}
+
+#[derive(Default, Debug)]
+pub struct Graph {
+    nodes: HashMap<String, usize>,
+    is_big: Vec<bool>,
+    adj: Vec<HashSet<usize>>,
+}
+
+impl Graph {
+    fn get_or_create_node(&mut self, key: &str) -> usize {
+        let new_id = self.nodes.len();
+
+        *self.nodes.entry(key.to_owned()).or_insert_with(|| {
+            self.adj.push(HashSet::new()); // create adjacency vector too
+            self.is_big.push(is_big(key));
+            new_id
+        })
+    }
+
+    fn connect(&mut self, from: &str, to: &str) {
+        let id_from = self.get_or_create_node(from);
+        let id_to = self.get_or_create_node(to);
+
+        self.adj[id_from].insert(id_to);
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        for adj_set in &mut self.adj {
+            adj_set.shrink_to_fit();
+        }
+        self.adj.shrink_to_fit();
+    }
+
+    pub fn key_of(&self, node_id: usize) -> &str {
+        self.nodes
+            .iter()
+            .find(|(_k, v)| **v == node_id)
+            .map(|(k, _)| k)
+            .unwrap()
+    }
+
+    pub fn count_paths(&self, from: &str, to: &str, can_visit_one_twice: bool) -> usize {
+        let from = self.nodes[from];
+        let to = self.nodes[to];
+
+        // where we are, the small caves visited so far (one bit each) and whether one of
+        // them can still be visited twice: as big caves only lead to small caves, every
+        // other step visits a new small cave or uses up the second visit, so no state is
+        // ever reached twice on the same path
+        type State = (usize, u128, bool);
+
+        // paths are counted once per distinct state, memoised, so events are per state
+        // rather than per cave visit, and there's no "leave" event: nothing runs once a
+        // state's paths are counted
+        let neighbours = |&(cave, visited, can_visit_twice): &State| {
+            crate::trace!(
+                12,
+                "expand state",
+                cave = self.key_of(cave),
+                can_visit_twice = can_visit_twice
+            );
+            self.adj[cave]
+                .iter()
+                .filter_map(move |&next| {
+                    if self.is_big[next] {
+                        Some((next, visited, can_visit_twice))
+                    } else if visited & 1 << next == 0 {
+                        Some((next, visited | 1 << next, can_visit_twice))
+                    } else if can_visit_twice && next != from {
+                        Some((next, visited, false))
+                    } else {
+                        None
+                    }
+                })
+                .map(|state| (state, 1))
+        };
+
+        let paths = search::count_paths(
+            &neighbours,
+            (from, 1 << from, can_visit_one_twice),
+            |&(cave, _, can_visit_twice)| {
+                if cave != to {
+                    return false;
+                }
+                crate::trace!(12, "goal state", can_visit_twice = can_visit_twice);
+                true
+            },
+        );
+        crate::trace!(12, "paths counted", count = paths);
+        paths
+    }
+}
+
+/// Small caves at most between `start` and `end`, about as many as in the puzzle's inputs.
+const MAX_CORE_SMALL: usize = 10;
+/// Big caves at most.
+const MAX_BIG: usize = 3;
+/// Dead ends at most hanging off each small cave.
+const MAX_DEAD_ENDS: usize = 2;
+
+/// Random cave system with `size` small caves (at most as many as `MAX_CAVES` allows),
+/// and a third as many big ones (but no more than `MAX_BIG`).
+///
+/// Big caves only ever connect to small caves, `start` or `end`: two connected big caves
+/// would allow infinitely many paths.
+///
+/// Paths multiply with every cave and connection between `start` and `end`, so only the
+/// first `MAX_CORE_SMALL` small caves are wired at random with the big ones, and the
+/// others hang off them as trees of dead ends (at most `MAX_DEAD_ENDS` per cave). A path
+/// can go into a dead end but only back out through a cave visited twice, so they add
+/// paths in proportion to their number, and inputs of any size solve in about the time of
+/// a puzzle input.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.clamp(1, MAX_CAVES - 2 - MAX_BIG);
+
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut _, big: bool| loop {
+        let name: String = (0..2)
+            .map(|_| {
+                let c = Rng::gen_range(rng, b'a'..=b'z') as char;
+                if big {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        if names.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let core_small = size.min(MAX_CORE_SMALL);
+    let mut caves = vec!["start".to_owned(), "end".to_owned()];
+    caves.extend((0..core_small).map(|_| name(rng, false)));
+    caves.extend((0..(core_small / 3 + 1).min(MAX_BIG)).map(|_| name(rng, true)));
+
+    let mut connections = HashSet::new();
+    let mut connect = |rng: &mut _, from: usize, count: usize| {
+        for _ in 0..count {
+            let to = Rng::gen_range(rng, 0..caves.len());
+            let (a, b) = (&caves[from], &caves[to]);
+            if from == to || (is_big(a) && is_big(b)) || (from < 2 && to < 2) {
+                continue;
+            }
+            connections.insert((from.min(to), from.max(to)));
+        }
+    };
+    connect(rng, 0, 2);
+    connect(rng, 1, 2);
+    for cave in 2..caves.len() {
+        connect(rng, cave, 2);
+    }
+    if !connections.iter().any(|&(a, _)| a == 0) {
+        connections.insert((0, 2));
+    }
+    if !connections.iter().any(|&(a, b)| a == 1 || b == 1) {
+        connections.insert((1, 2));
+    }
+
+    // small caves that can still have dead ends hanging off them
+    let mut open: Vec<(usize, usize)> = (2..2 + core_small).map(|cave| (cave, 0)).collect();
+    for _ in core_small..size {
+        let idx = rng.gen_range(0..open.len());
+        let (parent, dead_ends) = &mut open[idx];
+        let parent = *parent;
+        *dead_ends += 1;
+        if *dead_ends == MAX_DEAD_ENDS {
+            open.swap_remove(idx);
+        }
+
+        let cave = caves.len();
+        caves.push(name(rng, false));
+        connections.insert((parent, cave));
+        open.push((cave, 0));
+    }
+
+    let mut connections: Vec<_> = connections.into_iter().collect();
+    connections.sort_unstable();
+    connections.shuffle(rng);
+
+    let mut out = String::new();
+    for (a, b) in connections {
+        let (a, b) = if rng.gen() { (a, b) } else { (b, a) };
+        writeln!(out, "{}-{}", caves[a], caves[b]).unwrap();
+    }
+    out
+}
+
+#[test]
+fn generated_caves() {
+    use rand::SeedableRng;
+    use std::time::{Duration, Instant};
+
+    for size in [1, 10, 50, 1000] {
+        for seed in 0..3 {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+            let graph = parse(&generate(&mut rng, size)).unwrap();
+            assert!(graph.nodes.len() <= MAX_CAVES);
+
+            let start = Instant::now();
+            assert!(part2(&graph) >= part1(&graph));
+            // puzzle inputs take milliseconds in release builds
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "size {} seed {}: {:?}",
+                size,
+                seed,
+                start.elapsed()
+            );
+        }
+    }
+}
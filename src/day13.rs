@@ -0,0 +1,180 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    fmt::Write,
+};
+
+use rand::Rng;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+};
+
+pub type Input = (BTreeSet<(usize, usize)>, Vec<FoldAlongInstr>);
+
+/// Number of visible dots after the first fold.
+pub fn part1((matrix, fold_instructions): &Input) -> usize {
+    let mut matrix = matrix.clone();
+    fold_along(&mut matrix, fold_instructions[0]);
+
+    matrix.len()
+}
+
+/// The code formed by the dots after all folds, drawn with `#`.
+pub fn part2((matrix, fold_instructions): &Input) -> String {
+    let mut matrix = matrix.clone();
+    for &instr in fold_instructions {
+        fold_along(&mut matrix, instr);
+    }
+
+    render_matrix(&matrix)
+}
+
+pub fn fold_along(matrix: &mut BTreeSet<(usize, usize)>, instr: FoldAlongInstr) {
+    let to_fold: Vec<_> = matrix
+        .iter()
+        .copied()
+        .filter(|&(x, y)| match instr {
+            FoldAlongInstr::X(fold_x) => x > fold_x,
+            FoldAlongInstr::Y(fold_y) => y > fold_y,
+        })
+        .collect();
+
+    for (x, y) in to_fold {
+        matrix.remove(&(x, y));
+
+        match instr {
+            FoldAlongInstr::X(fold_x) => matrix.insert((2 * fold_x - x, y)),
+            FoldAlongInstr::Y(fold_y) => matrix.insert((x, 2 * fold_y - y)),
+        };
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum FoldAlongInstr {
+    X(usize),
+    Y(usize),
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(13, input);
+    let mut lines = crate::input::skip_blank_lines(input).lines();
+
+    let mut line = src.require(lines.next(), input, "a dot position")?;
+    let mut grid = BTreeSet::new();
+    while !line.trim().is_empty() {
+        let (x, y) = src.require(
+            line.trim().split_once(','),
+            line,
+            "a dot position like `x,y`",
+        )?;
+        let x = src.parse(x, "an x coordinate")?;
+        let y = src.parse(y, "a y coordinate")?;
+
+        grid.insert((x, y));
+        line = src.require(
+            lines.next(),
+            line,
+            "an empty line before the fold instructions",
+        )?;
+    }
+
+    let mut folds = Vec::new();
+    for line in lines.filter(|line| !line.is_empty()) {
+        let line = line.trim();
+        let (prefix, val) =
+            src.require(line.split_once('='), line, "a fold like `fold along x=5`")?;
+        let val = src.parse(val, "a fold position")?;
+
+        let val = match prefix {
+            "fold along x" => FoldAlongInstr::X(val),
+            "fold along y" => FoldAlongInstr::Y(val),
+            _ => return Err(src.error(prefix, "`fold along x` or `fold along y`")),
+        };
+        folds.push(val);
+    }
+
+    if grid.is_empty() {
+        return Err(src.error(input, "at least one dot"));
+    }
+    if folds.is_empty() {
+        return Err(src.error_at_end("at least one fold instruction"));
+    }
+
+    Ok((grid, folds))
+}
+
+pub fn render_matrix(matrix: &BTreeSet<(usize, usize)>) -> String {
+    let max_x = matrix.iter().map(|&(x, _y)| x).max().unwrap();
+    let max_y = matrix.iter().map(|&(_x, y)| y).max().unwrap();
+
+    Grid::from_fn(max_x + 1, max_y + 1, |(y, x)| {
+        if matrix.contains(&(x, y)) {
+            '#'
+        } else {
+            ' '
+        }
+    })
+    .to_string()
+}
+
+/// Random transparent paper with (up to) `size` dots, folded like the puzzle's: 1311x895
+/// folded in half 12 times, down to 40x6.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    // fold lines from the last fold to the first: each fold halves the paper exactly,
+    // leaving nothing past twice the fold line
+    let fold_lines = |mut len: usize, count| {
+        (0..count)
+            .map(|_| {
+                let line = len;
+                len = 2 * len + 1;
+                line
+            })
+            .collect::<Vec<_>>()
+    };
+    let xs = fold_lines(40, 5);
+    let ys = fold_lines(6, 7);
+    let (width, height) = (2 * xs[4] + 1, 2 * ys[6] + 1);
+
+    // puzzle order: alternate, starting along x
+    let mut folds = Vec::new();
+    let (mut x_folds, mut y_folds) = (xs.iter().rev(), ys.iter().rev());
+    loop {
+        match (x_folds.next(), y_folds.next()) {
+            (None, None) => break,
+            (x, y) => {
+                folds.extend(x.map(|x| format!("fold along x={}", x)));
+                folds.extend(y.map(|y| format!("fold along y={}", y)));
+            }
+        }
+    }
+
+    // no dot may ever end up on a fold line, neither before nor after the earlier folds
+    let hits_fold_line = |lines: &[usize], mut v: usize| {
+        lines.iter().rev().any(|&line| {
+            if v > line {
+                v = 2 * line - v;
+            }
+            v == line
+        })
+    };
+
+    let count = size.clamp(1, (40 << xs.len()) * (6 << ys.len()));
+    let mut dots = HashSet::new();
+    let mut out = String::new();
+    while dots.len() < count {
+        let dot = (rng.gen_range(0..width), rng.gen_range(0..height));
+        if hits_fold_line(&xs, dot.0) || hits_fold_line(&ys, dot.1) || !dots.insert(dot) {
+            continue;
+        }
+        writeln!(out, "{},{}", dot.0, dot.1).unwrap();
+    }
+
+    out.push('\n');
+    for fold in folds {
+        writeln!(out, "{}", fold).unwrap();
+    }
+    out
+}
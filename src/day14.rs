@@ -0,0 +1,233 @@
+use std::{collections::HashMap, fmt::Write, sync::mpsc::channel};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::error::{ParseError, Source};
+
+pub type Rules = HashMap<(char, char), char>;
+pub type Input = (Vec<char>, Rules);
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(14, input);
+    let mut lines = crate::input::skip_blank_lines(input).lines();
+
+    let template = src
+        .require(lines.next(), input, "a polymer template")?
+        .trim();
+    let initial: Vec<char> = template.chars().collect();
+    if initial.len() < 2 {
+        return Err(src.error(template, "a polymer template with at least 2 elements"));
+    }
+
+    let separator = src.require(lines.next(), template, "an empty line after the template")?;
+    if !separator.trim().is_empty() {
+        return Err(src.error(separator, "an empty line after the template"));
+    }
+
+    let parsed_rules = lines
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let (before, after) =
+                src.require(line.split_once(" -> "), line, "a rule like `AB -> C`")?;
+
+            let (c1, c2) = match before.chars().collect::<Vec<_>>()[..] {
+                [c1, c2] => (c1, c2),
+                _ => return Err(src.error(before, "a pair of elements")),
+            };
+            let c = match after.chars().collect::<Vec<_>>()[..] {
+                [c] => c,
+                _ => return Err(src.error(after, "a single element")),
+            };
+
+            Ok((line, (c1, c2), c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let rules: Rules = parsed_rules
+        .iter()
+        .map(|&(_line, pair, c)| (pair, c))
+        .collect();
+
+    // every pair that can ever show up must have a rule
+    if let Some(win) = initial
+        .windows(2)
+        .find(|w| !rules.contains_key(&(w[0], w[1])))
+    {
+        return Err(src.error(
+            template,
+            format!("a rule for the pair {}{}", win[0], win[1]),
+        ));
+    }
+    for &(line, (c1, c2), c) in &parsed_rules {
+        if let Some((a, b)) = [(c1, c), (c, c2)]
+            .into_iter()
+            .find(|pair| !rules.contains_key(pair))
+        {
+            return Err(src.error(line, format!("a rule for the pair {}{}", a, b)));
+        }
+    }
+
+    Ok((initial, rules))
+}
+
+/// Most common minus least common element after 10 steps, building the polymer explicitly.
+pub fn part1((initial, rules): &Input) -> usize {
+    let mut polymer = initial.clone();
+    for _ in 0..10 {
+        polymer = step_polymer(polymer, rules);
+    }
+
+    spread(&el_frequencies(&polymer))
+}
+
+/// Same as [`part1`], but tracking pair counts instead of building the polymer.
+pub fn part1_pair_counts((initial, rules): &Input) -> usize {
+    spread(&polymer_freqs_after_steps(initial, rules, 10))
+}
+
+/// Most common minus least common element after 40 steps, tracking pair counts.
+pub fn part2((initial, rules): &Input) -> usize {
+    spread(&polymer_freqs_after_steps(initial, rules, 40))
+}
+
+/// Same as [`part2`], but expanding every initial pair recursively in its own thread.
+pub fn part2_parallel((initial, rules): &Input) -> usize {
+    spread(&polymer_freqs_after_steps_parallel(initial, rules, 40))
+}
+
+/// Difference between the most and the least common element.
+pub fn spread(frequencies: &HashMap<char, usize>) -> usize {
+    let (_, &min) = frequencies.iter().min_by_key(|(_, &count)| count).unwrap();
+    let (_, &max) = frequencies.iter().max_by_key(|(_, &count)| count).unwrap();
+
+    max - min
+}
+
+pub fn step_polymer(polymer: Vec<char>, rules: &Rules) -> Vec<char> {
+    polymer
+        .windows(2)
+        .flat_map(|v| match *v {
+            [a, b, ..] => [a, rules[&(a, b)]],
+            _ => unreachable!(),
+        })
+        .chain(std::iter::once(polymer.iter().last().copied().unwrap()))
+        .collect()
+}
+
+pub fn el_frequencies(polymer: &[char]) -> HashMap<char, usize> {
+    let mut map = HashMap::new();
+
+    for &c in polymer {
+        *map.entry(c).or_default() += 1;
+    }
+
+    map
+}
+
+pub fn polymer_freqs_after_steps(
+    polymer: &[char],
+    rules: &Rules,
+    n: usize,
+) -> HashMap<char, usize> {
+    let mut pair_counts = rules
+        .keys()
+        .copied()
+        .map(|(a, b)| ((a, b), 0_usize))
+        .collect::<HashMap<_, _>>();
+
+    for win in polymer.windows(2) {
+        let start = win[0];
+        let end = win[1];
+        *pair_counts.get_mut(&(start, end)).unwrap() += 1;
+    }
+
+    for _ in 0..n {
+        let mut next_pair_counts = pair_counts.clone();
+        for (&(start, end), &count) in &pair_counts {
+            *next_pair_counts.get_mut(&(start, end)).unwrap() -= count;
+
+            let middle = rules[&(start, end)];
+            *next_pair_counts.get_mut(&(start, middle)).unwrap() += count;
+            *next_pair_counts.get_mut(&(middle, end)).unwrap() += count;
+        }
+
+        pair_counts = next_pair_counts;
+    }
+
+    let mut counts = HashMap::new();
+    // pairs that never formed must not bring their element in with a count of 0
+    for ((start, _end), count) in pair_counts.into_iter().filter(|&(_, count)| count > 0) {
+        *counts.entry(start).or_default() += count;
+    }
+
+    // add last letter too!
+    *counts.entry(*polymer.last().unwrap()).or_default() += 1;
+
+    counts
+}
+
+pub fn polymer_freqs_after_steps_parallel(
+    polymer: &[char],
+    rules: &Rules,
+    n: usize,
+) -> HashMap<char, usize> {
+    fn recurse(start: char, end: char, rules: &Rules, n: usize, counts: &mut HashMap<char, usize>) {
+        if n == 0 {
+            *counts.entry(start).or_default() += 1;
+        } else {
+            let middle = rules[&(start, end)];
+            recurse(start, middle, rules, n - 1, counts);
+            recurse(middle, end, rules, n - 1, counts);
+        }
+    }
+
+    let (tx, rx) = channel();
+    std::thread::scope(|scope| {
+        for win in polymer.windows(2) {
+            let start = win[0];
+            let end = win[1];
+            let tx = tx.clone();
+            scope.spawn(move || {
+                let mut counts_local = HashMap::new();
+                recurse(start, end, rules, n, &mut counts_local);
+                tx.send(counts_local).unwrap();
+            });
+        }
+    });
+    std::mem::drop(tx); // make sure the channel is closed after the last thread completes
+
+    let mut counts = HashMap::new();
+    for count in rx.into_iter() {
+        for (c, count) in count {
+            *counts.entry(c).or_default() += count;
+        }
+    }
+
+    // add last letter too!
+    *counts.entry(*polymer.last().unwrap()).or_default() += 1;
+
+    counts
+}
+
+/// Random polymer template of `size` elements, with rules for every pair of the same ten
+/// elements as the puzzle.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    const ELEMENTS: [char; 10] = ['B', 'C', 'F', 'H', 'K', 'N', 'O', 'P', 'S', 'V'];
+
+    let mut out: String = (0..size.max(2))
+        .map(|_| *ELEMENTS.choose(rng).unwrap())
+        .collect();
+    out.push_str("\n\n");
+
+    let mut pairs: Vec<(char, char)> = ELEMENTS
+        .iter()
+        .flat_map(|&a| ELEMENTS.iter().map(move |&b| (a, b)))
+        .collect();
+    pairs.shuffle(rng);
+    for (a, b) in pairs {
+        writeln!(out, "{}{} -> {}", a, b, ELEMENTS.choose(rng).unwrap()).unwrap();
+    }
+    out
+}
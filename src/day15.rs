@@ -0,0 +1,61 @@
+use rand::Rng;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::{Grid, Pos},
+    search::{self, Cost},
+};
+
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    Grid::parse_digits(&Source::new(15, input), input, 1..=9, "risk level")
+}
+
+/// Lowest total risk of any path from the top left to the bottom right.
+pub fn part1(map: &Grid<u8>) -> isize {
+    search_min_path(map)
+}
+
+/// Lowest total risk on the full map (the input tiled 5 times in each direction).
+pub fn part2(map: &Grid<u8>) -> isize {
+    search_min_path(&augment_map(map))
+}
+
+pub fn search_min_path(map: &Grid<u8>) -> isize {
+    lowest_risk_path(map).0
+}
+
+/// Lowest total risk from the top left to the bottom right, and the positions along the
+/// path (both ends included).
+pub fn lowest_risk_path(map: &Grid<u8>) -> (isize, Vec<Pos>) {
+    let end = (map.height() - 1, map.width() - 1);
+
+    // "the starting position is never entered, so its risk is not counted": each step
+    // costs the risk of the position it enters
+    let neighbours = |&pos: &Pos| map.neighbours4(pos).map(|next| (next, map[next] as Cost));
+    // every step costs at least 1, so this never overestimates
+    let steps_left = |&(row, col): &Pos| ((end.0 - row) + (end.1 - col)) as Cost;
+
+    let (risk, path) = search::astar(&neighbours, (0, 0), |&pos| pos == end, steps_left)
+        .goal_path()
+        .unwrap();
+    (risk as isize, path)
+}
+
+pub fn augment_map(map: &Grid<u8>) -> Grid<u8> {
+    let aug_map = Grid::from_fn(map.width() * 5, map.height() * 5, |(row, col)| {
+        let offset = (row / map.height() + col / map.width()) as u8;
+        let val = map[(row % map.height(), col % map.width())];
+        (val - 1 + offset) % 9 + 1
+    });
+
+    assert!(aug_map.iter().all(|&v| v < 10));
+
+    aug_map
+}
+
+/// Random `size`x`size` risk level map.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    crate::day9::digit_grid(rng, size.max(1), |rng| rng.gen_range(1..=9))
+}
@@ -0,0 +1,514 @@
+use bitvec::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use std::fmt::{self, Display};
+
+use crate::error::{ParseError, Source};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Packet {
+    Literal {
+        version: u8,
+        parts: Vec<u8>,
+    },
+    Operator {
+        version: u8,
+        typ: OperatorType,
+        subpackets: Vec<Packet>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperatorType {
+    Sum,
+    Product,
+    Minimum,
+    Maximum,
+    GreaterThan,
+    LessThan,
+    EqualTo,
+}
+
+/*
+Header Format:
+|0  ...  2|3  ...  5|6 ..
+| Version | Type ID |
+
+Payload format (TypeId = 4):
+|6 .. 10|11 .. 15|16 .. 20|
+|   A   |   B    |   C    |
+
+Payload format (for TypeID != 4, length type 0):
+|          6         |7      ..      21|22 .. (22+subpacket_length-1)|
+| Length Type ID = 0 |Sub-packet length|         Sub-packets         |
+
+Payload format (for TypeID != 4, length type 1):
+|          6         |7      ..      17|18 ..                        |
+| Length Type ID = 1 |Sub-packet  count|     <count> Sub-packets     |
+*/
+
+/// Where (in bits) and why a transmission could not be decoded.
+#[derive(Debug)]
+struct PacketError {
+    bit: usize,
+    expected: &'static str,
+}
+
+impl PacketError {
+    /// Moves the error by `n` bits, for errors that come from a subslice starting at `n`.
+    fn shifted(self, n: usize) -> Self {
+        PacketError {
+            bit: self.bit + n,
+            ..self
+        }
+    }
+}
+
+type PacketResult<T> = Result<T, PacketError>;
+
+/// An operator that cannot be encoded: its sub-packets are too long for the 15-bit length
+/// field and too many for the 11-bit count field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeError {
+    pub subpackets: usize,
+    /// Length of the encoded sub-packets.
+    pub bits: usize,
+}
+
+impl Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot encode an operator with {} sub-packets taking {} bits",
+            self.subpackets, self.bits
+        )
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Bits `start..start + len` of `bits`, or an error if the transmission ends before that.
+fn field<'a, S: BitStore>(
+    bits: &'a BitSlice<Msb0, S>,
+    start: usize,
+    len: usize,
+    expected: &'static str,
+) -> PacketResult<&'a BitSlice<Msb0, S>> {
+    if start + len <= bits.len() {
+        Ok(&bits[start..start + len])
+    } else {
+        Err(PacketError {
+            bit: start.min(bits.len()),
+            expected,
+        })
+    }
+}
+
+pub fn parse_packet(src: &Source, line: &str) -> Result<Packet, ParseError> {
+    let line = line.trim();
+    let bytes = hex::decode(line).map_err(|e| match e {
+        hex::FromHexError::InvalidHexCharacter { index, .. } => {
+            src.error(&line[index..], "a hexadecimal digit")
+        }
+        _ => src.error_after(line, "an even number of hexadecimal digits"),
+    })?;
+    let bits = bytes.view_bits::<Msb0>();
+
+    parse_packet_from_bits(bits)
+        .map(|(packet, _size)| packet)
+        .map_err(|e| src.error(&line[(e.bit / 4).min(line.len())..], e.expected))
+}
+
+fn parse_packet_from_bits<S: BitStore>(bits: &BitSlice<Msb0, S>) -> PacketResult<(Packet, usize)> {
+    let version = field(bits, 0, 3, "a packet version")?.load_be();
+    let type_id: u8 = field(bits, 3, 3, "a packet type id")?.load_be();
+
+    let (packet, size) = match type_id {
+        4 => parse_literal_packet(version, bits)?,
+        _ => parse_operator_packet(type_id, version, bits)?,
+    };
+
+    Ok((packet, size))
+}
+
+fn parse_literal_packet<S: BitStore>(
+    version: u8,
+    bits: &BitSlice<Msb0, S>,
+) -> PacketResult<(Packet, usize)> {
+    let mut parts = Vec::new();
+
+    loop {
+        // bit 0 is the "do I have more" flag
+        // bits 1-4 are part of the number
+        let group = field(bits, 6 + parts.len() * 5, 5, "a literal value group")?;
+        parts.push(group[1..5].load_be());
+
+        if !group[0] {
+            // this was the last section
+            break;
+        }
+    }
+
+    if parts.len() > 16 {
+        return Err(PacketError {
+            bit: 6,
+            expected: "a literal value that fits in 64 bits",
+        });
+    }
+
+    let size = 6 + parts.len() * 5;
+
+    // aligned to nibs, account for padding
+    //size += size % 4;
+
+    Ok((Packet::Literal { version, parts }, size))
+}
+
+fn parse_operator_packet<S: BitStore>(
+    type_id: u8,
+    version: u8,
+    bits: &BitSlice<Msb0, S>,
+) -> PacketResult<(Packet, usize)> {
+    debug_assert_ne!(type_id, 4);
+
+    let length_type_id = field(bits, 6, 1, "a length type id")?[0];
+    let mut size = 7;
+
+    let (subpackets, sub_size) = if length_type_id {
+        let count: u16 = field(bits, 7, 11, "a sub-packet count")?.load_be();
+        size += 11;
+        parse_subpackets_until_count(count as usize, &bits[18..]).map_err(|e| e.shifted(18))?
+    } else {
+        let len: u16 = field(bits, 7, 15, "a sub-packet length")?.load_be();
+        size += 15;
+        parse_subpackets_until_len(len as usize, &bits[22..]).map_err(|e| e.shifted(22))?
+    };
+    size += sub_size;
+
+    let typ = match type_id {
+        0 => OperatorType::Sum,
+        1 => OperatorType::Product,
+        2 => OperatorType::Minimum,
+        3 => OperatorType::Maximum,
+        5 => OperatorType::GreaterThan,
+        6 => OperatorType::LessThan,
+        7 => OperatorType::EqualTo,
+        _ => unreachable!("type ids only have 3 bits"),
+    };
+
+    let expected = match typ {
+        OperatorType::Minimum | OperatorType::Maximum if subpackets.is_empty() => {
+            Some("at least one sub-packet")
+        }
+        OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo
+            if subpackets.len() != 2 =>
+        {
+            Some("exactly two sub-packets for a comparison")
+        }
+        _ => None,
+    };
+    if let Some(expected) = expected {
+        return Err(PacketError { bit: 6, expected });
+    }
+
+    Ok((
+        Packet::Operator {
+            version,
+            typ,
+            subpackets,
+        },
+        size,
+    ))
+}
+
+fn parse_subpackets_until_len<S: BitStore>(
+    len: usize,
+    mut bits: &BitSlice<Msb0, S>,
+) -> PacketResult<(Vec<Packet>, usize)> {
+    let mut packets = Vec::new();
+    let mut n_read = 0_usize;
+
+    while n_read < len {
+        let (sub, sub_len) = parse_packet_from_bits(bits).map_err(|e| e.shifted(n_read))?;
+
+        packets.push(sub);
+        bits = &bits[sub_len..];
+        n_read += sub_len;
+    }
+
+    if n_read != len {
+        return Err(PacketError {
+            bit: len,
+            expected: "sub-packets that end at the announced length",
+        });
+    }
+
+    packets.shrink_to_fit();
+    Ok((packets, n_read))
+}
+
+fn parse_subpackets_until_count<S: BitStore>(
+    count: usize,
+    mut bits: &BitSlice<Msb0, S>,
+) -> PacketResult<(Vec<Packet>, usize)> {
+    let mut packets = Vec::with_capacity(count);
+    let mut size = 0;
+
+    while packets.len() != count {
+        let (sub, sub_len) = parse_packet_from_bits(bits).map_err(|e| e.shifted(size))?;
+
+        packets.push(sub);
+        bits = &bits[sub_len..];
+        size += sub_len;
+    }
+
+    Ok((packets, size))
+}
+
+/// Parses the transmission on the first line of the input.
+pub fn parse(input: &str) -> Result<Packet, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(16, input);
+    let line = src.require(
+        crate::input::skip_blank_lines(input).lines().next(),
+        input,
+        "a hexadecimal transmission",
+    )?;
+    parse_packet(&src, line).inspect(|packet| crate::trace!(16, "parsed", packet = packet))
+}
+
+/// Sum of the version numbers of every packet.
+pub fn part1(packet: &Packet) -> u64 {
+    packet_version_sum(packet)
+}
+
+/// Value of the expression the outermost packet represents.
+pub fn part2(packet: &Packet) -> u64 {
+    compute_value(packet)
+}
+
+pub fn packet_version_sum(packet: &Packet) -> u64 {
+    match packet {
+        Packet::Literal { version, .. } => *version as u64,
+        Packet::Operator {
+            version,
+            subpackets,
+            ..
+        } => *version as u64 + subpackets.iter().map(packet_version_sum).sum::<u64>(),
+    }
+}
+
+pub fn compute_value(packet: &Packet) -> u64 {
+    match packet {
+        Packet::Literal { parts, .. } => parts.iter().fold(0_u64, |acc, &x| acc * 16 + x as u64),
+        Packet::Operator {
+            typ, subpackets, ..
+        } => {
+            use OperatorType::*;
+            match typ {
+                Sum => subpackets.iter().map(compute_value).sum::<u64>(),
+                Product => subpackets.iter().map(compute_value).product::<u64>(),
+                Minimum => subpackets.iter().map(compute_value).min().unwrap(),
+                Maximum => subpackets.iter().map(compute_value).max().unwrap(),
+                GreaterThan if subpackets.len() == 2 => {
+                    if compute_value(&subpackets[0]) > compute_value(&subpackets[1]) {
+                        1
+                    } else {
+                        0
+                    }
+                }
+                LessThan if subpackets.len() == 2 => {
+                    if compute_value(&subpackets[0]) < compute_value(&subpackets[1]) {
+                        1
+                    } else {
+                        0
+                    }
+                }
+                EqualTo if subpackets.len() == 2 => {
+                    if compute_value(&subpackets[0]) == compute_value(&subpackets[1]) {
+                        1
+                    } else {
+                        0
+                    }
+                }
+                GreaterThan | LessThan | EqualTo => {
+                    unreachable!("invalid operand count for comparator")
+                }
+            }
+        }
+    }
+}
+
+impl Packet {
+    /// Encodes the packet as a hexadecimal transmission, padded with zero bits to a whole
+    /// number of bytes.
+    ///
+    /// Operators give the length of their sub-packets in bits when it fits in the 15-bit
+    /// field, and their number when that fits in the 11-bit field; an operator with neither
+    /// is an error.
+    pub fn to_hex(&self) -> Result<String, EncodeError> {
+        let mut bits = BitVec::<Msb0, u8>::new();
+        self.encode(&mut bits)?;
+        while !bits.len().is_multiple_of(8) {
+            bits.push(false);
+        }
+        Ok(hex::encode_upper(bits.into_vec()))
+    }
+
+    fn encode(&self, bits: &mut BitVec<Msb0, u8>) -> Result<(), EncodeError> {
+        match self {
+            Packet::Literal { version, parts } => {
+                push_field(bits, *version as u64, 3);
+                push_field(bits, 4, 3);
+                for (idx, &part) in parts.iter().enumerate() {
+                    bits.push(idx + 1 < parts.len());
+                    push_field(bits, part as u64, 4);
+                }
+            }
+            Packet::Operator {
+                version,
+                typ,
+                subpackets,
+            } => {
+                push_field(bits, *version as u64, 3);
+                let type_id = match typ {
+                    OperatorType::Sum => 0,
+                    OperatorType::Product => 1,
+                    OperatorType::Minimum => 2,
+                    OperatorType::Maximum => 3,
+                    OperatorType::GreaterThan => 5,
+                    OperatorType::LessThan => 6,
+                    OperatorType::EqualTo => 7,
+                };
+                push_field(bits, type_id, 3);
+
+                let mut sub_bits = BitVec::<Msb0, u8>::new();
+                for p in subpackets {
+                    p.encode(&mut sub_bits)?;
+                }
+                if sub_bits.len() < 1 << 15 {
+                    bits.push(false);
+                    push_field(bits, sub_bits.len() as u64, 15);
+                } else if subpackets.len() < 1 << 11 {
+                    bits.push(true);
+                    push_field(bits, subpackets.len() as u64, 11);
+                } else {
+                    return Err(EncodeError {
+                        subpackets: subpackets.len(),
+                        bits: sub_bits.len(),
+                    });
+                }
+                bits.extend_from_bitslice(&sub_bits);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Appends the `width` lowest bits of `value`, most significant first.
+fn push_field(bits: &mut BitVec<Msb0, u8>, value: u64, width: usize) {
+    for i in (0..width).rev() {
+        bits.push(value >> i & 1 == 1);
+    }
+}
+
+/// Random transmission of about `size` packets. Values are kept small enough that the
+/// expression can be evaluated without overflowing.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    generate_packet(rng, size.max(1), u64::MAX)
+        .to_hex()
+        .expect("generated operators have at most 5 sub-packets")
+        + "\n"
+}
+
+/// Random packet made of `size` packets (give or take), whose value is at most `max_value`.
+fn generate_packet(rng: &mut impl Rng, size: usize, max_value: u64) -> Packet {
+    use OperatorType::*;
+
+    let version = rng.gen_range(0..8);
+    let typ = [
+        Sum,
+        Product,
+        Minimum,
+        Maximum,
+        GreaterThan,
+        LessThan,
+        EqualTo,
+    ]
+    .choose(rng)
+    .copied()
+    .unwrap();
+
+    let is_comparison = matches!(typ, GreaterThan | LessThan | EqualTo);
+    if size == 1 || (is_comparison && (size < 3 || max_value == 0)) {
+        let groups = rng.gen_range(1..=5);
+        let value = rng.gen_range(0..=max_value.min((1_u64 << (4 * groups)) - 1));
+        let mut parts: Vec<u8> = (0..16)
+            .map(|i| (value >> (4 * (15 - i)) & 0xF) as u8)
+            .skip_while(|&part| part == 0)
+            .collect();
+        if parts.is_empty() {
+            parts.push(0);
+        }
+        return Packet::Literal { version, parts };
+    }
+
+    let count = if is_comparison {
+        2
+    } else {
+        rng.gen_range(1..=(size - 1).min(5))
+    };
+    let sub_max = match typ {
+        Sum => max_value / count as u64,
+        Product => {
+            // largest n such that n^count <= max_value
+            let mut root = (max_value as f64).powf(1.0 / count as f64) as u64;
+            while root > 0 && root.checked_pow(count as u32).is_none_or(|p| p > max_value) {
+                root -= 1;
+            }
+            root
+        }
+        _ => max_value,
+    };
+
+    // share the remaining packets, at least one for each sub-packet
+    let mut sizes = vec![1; count];
+    for _ in count..size - 1 {
+        sizes[rng.gen_range(0..count)] += 1;
+    }
+
+    let subpackets = sizes
+        .into_iter()
+        .map(|size| generate_packet(rng, size, sub_max))
+        .collect();
+    Packet::Operator {
+        version,
+        typ,
+        subpackets,
+    }
+}
+
+#[test]
+fn long_operators() {
+    // 31 bits per literal: 1100 of them overflow the length field, 3000 the count field too
+    let sum = |count| Packet::Operator {
+        version: 0,
+        typ: OperatorType::Sum,
+        subpackets: vec![
+            Packet::Literal {
+                version: 0,
+                parts: vec![1, 0, 0, 0, 0],
+            };
+            count
+        ],
+    };
+    let hex = sum(1100).to_hex().unwrap();
+    assert_eq!(parse(&hex).unwrap(), sum(1100));
+    assert_eq!(
+        sum(3000).to_hex(),
+        Err(EncodeError {
+            subpackets: 3000,
+            bits: 3000 * 31
+        })
+    );
+}
@@ -0,0 +1,192 @@
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::error::{ParseError, Source};
+
+pub type TargetArea = [RangeInclusive<i64>; 2];
+
+/// Parses a target area description like `target area: x=20..30, y=-10..-5`.
+pub fn parse(input: &str) -> Result<TargetArea, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(17, input);
+    let input = input.trim();
+
+    let ranges = input
+        .strip_prefix("target area: ")
+        .ok_or_else(|| src.error(input, "`target area: `"))?;
+    let (x, y) = src.require(
+        ranges.split_once(", "),
+        ranges,
+        "`, ` between the x and y ranges",
+    )?;
+
+    let parse_range = |s: &str, axis: &str| {
+        let range = s
+            .strip_prefix(axis)
+            .ok_or_else(|| src.error(s, format!("`{}`", axis)))?;
+        let (start, end) = src.require(range.split_once(".."), range, "a range like `1..2`")?;
+        let (start, end) = (
+            src.parse::<i64>(start, "an integer")?,
+            src.parse::<i64>(end, "an integer")?,
+        );
+        if start > end {
+            return Err(src.error(range, "a range whose start is not after its end"));
+        }
+
+        Ok(start..=end)
+    };
+
+    let target_area = [parse_range(x, "x=")?, parse_range(y, "y=")?];
+    if *target_area[0].start() <= 0 || *target_area[1].end() >= 0 {
+        // velocity_limits relies on it
+        return Err(src.error(ranges, "a target area below and to the right of the probe"));
+    }
+
+    Ok(target_area)
+}
+
+/// Highest y position reached by any trajectory that hits the target area.
+pub fn part1(target_area: &TargetArea) -> i64 {
+    launch_all([0, 0], target_area).0
+}
+
+/// Number of distinct initial velocities that hit the target area.
+pub fn part2(target_area: &TargetArea) -> usize {
+    launch_all([0, 0], target_area).1
+}
+
+/// Tries every plausible initial velocity, returning the highest y reached by a
+/// trajectory that hits the target and how many initial velocities hit it.
+fn launch_all(start_pos: [i64; 2], target_area: &TargetArea) -> (i64, usize) {
+    let [v0x_range, v0y_range] = velocity_limits(start_pos, target_area);
+
+    let mut combo_count = 0;
+    let mut max_y = i64::MIN;
+    for v0x in v0x_range {
+        for v0y in v0y_range.clone() {
+            // vy(t) = v0y - t
+            // vy = 0 <=> t = v0y
+            // y grows in t < v0y and decreases for t > v0y
+
+            let mut x = start_pos[0];
+            let mut y = start_pos[1];
+            let mut vx = v0x;
+            let mut vy = v0y;
+            // the probe starts there, even when it only ever goes down
+            let mut tentative_max_y = start_pos[1];
+            for t in 0_i64.. {
+                x += vx;
+                y += vy;
+                vx -= vx.signum();
+                vy -= 1;
+
+                if y > tentative_max_y {
+                    tentative_max_y = y;
+                }
+
+                if t > v0y && y < *target_area[1].start() {
+                    // no target
+                    break;
+                }
+
+                if target_area[0].contains(&x) && target_area[1].contains(&y) {
+                    // reached target
+                    combo_count += 1;
+
+                    if tentative_max_y > max_y {
+                        max_y = tentative_max_y;
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    (max_y, combo_count)
+}
+
+fn velocity_limits(start_pos: [i64; 2], target_area: &TargetArea) -> [RangeInclusive<i64>; 2] {
+    let v0x_range = v0x_limits(start_pos[0], &target_area[0]);
+    let v0y_range = v0y_limits(start_pos[1], &target_area[1]);
+
+    [v0x_range, v0y_range]
+}
+
+/*
+discrete calculations mean only positions
+0, v0x, 2v0x-1, 3v0x-3, ..., 1/2 v0x^2 + 1/2 v0x will ever be hit (same for y going up)
+and the maximum height/length will be at t=v0x (for x, t=v0y for y)
+sooo v0x <= xmax
+*/
+
+fn v0x_limits(start_pos: i64, target_range: &RangeInclusive<i64>) -> RangeInclusive<i64> {
+    let min = if start_pos < *target_range.start() {
+        // must have some speed to get to a proper position
+        1
+    } else {
+        // we can go down to xmin at step 1
+        i64::min(*target_range.start(), 0)
+    };
+
+    let max = if start_pos == *target_range.end() {
+        0
+    } else if start_pos <= *target_range.end() {
+        *target_range.end()
+    } else {
+        unreachable!()
+    };
+
+    min..=max
+}
+
+fn v0y_limits(_start_pos: i64, target_range: &RangeInclusive<i64>) -> RangeInclusive<i64> {
+    let min = {
+        // we can go down to ymin at step 1
+        i64::min(*target_range.start(), 0)
+    };
+
+    assert!(*target_range.start() < 0); // this reasoning assumes it
+    let max = {
+        // at t=2*v0y, y reaches 0 again
+        // at t=2*v0y+1 y reaches -v0y-1
+        // so we must ensure it doesn't go beyond ymin
+        // -v0y-1 >= ymin <=> v0y <= -ymin-1
+        -*target_range.start() - 1
+    };
+    min..=max
+}
+
+/// Random target area, below and to the right of the probe, at most about `size` away
+/// from it on each axis.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(10) as i64;
+    let x_start = rng.gen_range(1..=size);
+    let x_end = x_start + rng.gen_range(0..=size / 4);
+    let y_end = -rng.gen_range(1..=size);
+    let y_start = y_end - rng.gen_range(0..=size / 4);
+
+    format!(
+        "target area: x={}..{}, y={}..{}\n",
+        x_start, x_end, y_start, y_end
+    )
+}
+
+#[test]
+fn launches() {
+    use rand::SeedableRng;
+
+    // only a shot going down hits it (20,-1), the highest point is the start
+    let close = parse("target area: x=20..20, y=-1..-1").unwrap();
+    assert_eq!(part1(&close), 0);
+    assert_eq!(part2(&close), 1);
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+    for size in [1, 10, 40] {
+        let target = parse(&generate(&mut rng, size)).unwrap();
+        assert!(*target[0].start() > 0 && *target[1].end() < 0);
+        assert!(part1(&target) >= 0);
+        assert!(part2(&target) > 0);
+    }
+}
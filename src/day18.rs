@@ -0,0 +1,321 @@
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit1, space0};
+use nom::combinator::{all_consuming, map, map_res};
+use nom::sequence::{delimited, separated_pair};
+use nom::Finish;
+use rand::Rng;
+use std::fmt::{Debug, Display, Write};
+
+use crate::error::{ParseError, Source};
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum SNum {
+    Lit(i64),
+    Pair(Box<[SNum; 2]>),
+}
+
+pub fn parse(input: &str) -> Result<Vec<SNum>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(18, input);
+    let numbers = input
+        .lines()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| {
+            all_consuming(parse_snum)(s.trim())
+                .finish()
+                .map(|(_rem, snum)| snum)
+                .map_err(|e| src.error(e.input, "a snailfish number like `[[1,2],3]`"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if numbers.is_empty() {
+        return Err(src.error_at_end("at least one snailfish number"));
+    }
+
+    Ok(numbers)
+}
+
+/// Magnitude of the sum of every number, in order.
+pub fn part1(numbers: &[SNum]) -> i64 {
+    numbers
+        .iter()
+        .cloned()
+        .reduce(|a, b| a.add(b))
+        .unwrap()
+        .magnitude()
+}
+
+/// Largest magnitude of the sum of any two different numbers.
+pub fn part2(numbers: &[SNum]) -> i64 {
+    let mut max_magnitude = 0;
+    for (i, n1) in numbers.iter().enumerate() {
+        for n2 in &numbers[i + 1..] {
+            {
+                let (n1, n2) = (n1.clone(), n2.clone());
+                let m = n1.add(n2).magnitude();
+                if m > max_magnitude {
+                    max_magnitude = m;
+                }
+            }
+            {
+                let (n1, n2) = (n1.clone(), n2.clone());
+                let m = n2.add(n1).magnitude();
+                if m > max_magnitude {
+                    max_magnitude = m;
+                }
+            }
+        }
+    }
+
+    max_magnitude
+}
+
+impl SNum {
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, other: SNum) -> SNum {
+        let mut n = SNum::Pair(Box::new([self, other]));
+        n.reduce();
+        n
+    }
+
+    fn reduce(&mut self) {
+        let mut changed = true;
+
+        while changed {
+            while self.reduce_once().is_some() {
+                // reduce until you can't reduce anymore
+                crate::trace!(18, "reduced", number = self.to_string());
+            }
+
+            changed = self.try_split().is_some();
+        }
+    }
+
+    fn reduce_once(&mut self) -> Option<()> {
+        const MAX_EXPLOSION_DEPTH_DIFF: usize = 4;
+
+        fn recurse(lvl1: [&mut SNum; 2], depth: usize) -> Option<([bool; 2], [i64; 2])> {
+            let [left1, right1] = lvl1;
+            if let SNum::Pair(boxed_pair) = left1 {
+                let [ref mut left2, ref mut right2] = **boxed_pair;
+
+                if left2.literal_mut().is_some()
+                    && right2.literal_mut().is_some()
+                    && depth >= MAX_EXPLOSION_DEPTH_DIFF
+                {
+                    // begin explosion
+
+                    let val_left = *left2.literal_mut().unwrap();
+                    let val_right = *right2.literal_mut().unwrap();
+                    *left1 = SNum::Lit(0);
+
+                    let mut right_set = false;
+                    if let Some(rval) = right1.first_lit_left() {
+                        *rval += val_right;
+                        right_set = true;
+                    }
+
+                    return Some(([false, right_set], [val_left, val_right]));
+                } else if let Some(([left_set, mut right_set], [val_left, val_right])) =
+                    recurse([left2, right2], depth + 1)
+                {
+                    if !right_set {
+                        if let Some(rval) = right1.first_lit_left() {
+                            *rval += val_right;
+                            right_set = true;
+                        }
+                    }
+
+                    return Some(([left_set, right_set], [val_left, val_right]));
+                }
+            }
+
+            if let SNum::Pair(boxed_pair) = right1 {
+                let [ref mut left2, ref mut right2] = **boxed_pair;
+
+                if left2.literal_mut().is_some()
+                    && right2.literal_mut().is_some()
+                    && depth >= MAX_EXPLOSION_DEPTH_DIFF
+                {
+                    // begin explosion
+
+                    let val_left = *left2.literal_mut().unwrap();
+                    let val_right = *right2.literal_mut().unwrap();
+                    *right1 = SNum::Lit(0);
+
+                    let mut left_set = false;
+                    if let Some(lval) = left1.first_lit_right() {
+                        *lval += val_left;
+                        left_set = true;
+                    }
+
+                    return Some(([left_set, false], [val_left, val_right]));
+                } else if let Some(([mut left_set, right_set], [val_left, val_right])) =
+                    recurse([left2, right2], depth + 1)
+                {
+                    if !left_set {
+                        if let Some(lval) = left1.first_lit_right() {
+                            *lval += val_left;
+                            left_set = true;
+                        }
+                    }
+
+                    return Some(([left_set, right_set], [val_left, val_right]));
+                }
+            }
+
+            None
+        }
+
+        match self {
+            SNum::Pair(boxed_pair) => {
+                let [ref mut left, ref mut right] = **boxed_pair;
+                recurse([left, right], 1).map(|_| ())
+            }
+            _ => None,
+        }
+    }
+
+    fn try_split(&mut self) -> Option<()> {
+        match self {
+            SNum::Pair(boxed_pair) => {
+                let [ref mut left, ref mut right] = **boxed_pair;
+                left.try_split().or_else(|| right.try_split())
+            }
+            SNum::Lit(n) => {
+                let n = *n;
+                if n >= 10 {
+                    let rounded_down = n / 2;
+                    let rounded_up = rounded_down + n % 2;
+
+                    let rounded_down = SNum::Lit(rounded_down);
+                    let rounded_up = SNum::Lit(rounded_up);
+                    *self = SNum::Pair(Box::new([rounded_down, rounded_up]));
+                    Some(())
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn literal_mut(&mut self) -> Option<&mut i64> {
+        match self {
+            SNum::Lit(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    fn first_lit_left(&mut self) -> Option<&mut i64> {
+        match self {
+            SNum::Lit(n) => Some(n),
+            SNum::Pair(boxed_pair) => {
+                let [ref mut left, ref mut right] = **boxed_pair;
+                left.first_lit_left().or_else(|| right.first_lit_left())
+            }
+        }
+    }
+
+    fn first_lit_right(&mut self) -> Option<&mut i64> {
+        match self {
+            SNum::Lit(n) => Some(n),
+            SNum::Pair(boxed_pair) => {
+                let [ref mut left, ref mut right] = **boxed_pair;
+                right.first_lit_right().or_else(|| left.first_lit_right())
+            }
+        }
+    }
+
+    pub fn magnitude(&self) -> i64 {
+        match self {
+            &SNum::Lit(n) => n,
+            SNum::Pair(boxed_pair) => {
+                let [ref left, ref right] = **boxed_pair;
+                3 * left.magnitude() + 2 * right.magnitude()
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for SNum {
+    type Err = nom::error::Error<String>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use nom::error::Error;
+        match parse_snum(s).finish() {
+            Ok((_rem, snum)) => Ok(snum),
+            Err(Error { input, code }) => Err(Error {
+                input: input.to_string(),
+                code,
+            }),
+        }
+    }
+}
+
+fn parse_i64(s: &str) -> nom::IResult<&str, i64> {
+    map_res(digit1, |input: &str| input.parse::<i64>())(s)
+}
+
+fn parse_snum(s: &str) -> nom::IResult<&str, SNum> {
+    alt((
+        map(parse_i64, SNum::Lit),
+        map(
+            delimited(
+                tag("["),
+                // Display puts a space after the comma
+                separated_pair(parse_snum, delimited(space0, tag(","), space0), parse_snum),
+                tag("]"),
+            ),
+            |res| SNum::Pair(Box::new([res.0, res.1])),
+        ),
+    ))(s)
+}
+
+impl std::fmt::Debug for SNum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lit(arg0) => Debug::fmt(arg0, f),
+            Self::Pair(boxed_pair) => {
+                let [ref left, ref right] = **boxed_pair;
+                f.debug_list().entry(left).entry(right).finish()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for SNum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Lit(arg0) => Display::fmt(arg0, f),
+            Self::Pair(boxed_pair) => {
+                let [ref left, ref right] = **boxed_pair;
+                f.debug_list().entry(left).entry(right).finish()
+            }
+        }
+    }
+}
+
+/// Random homework of `size` reduced snailfish numbers.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(2) {
+        let number = generate_snum(rng, 0).to_string().replace(' ', "");
+        writeln!(out, "{}", number).unwrap();
+    }
+    out
+}
+
+/// Random reduced number nested inside `depth` pairs: no pair is nested inside four
+/// pairs, and no regular number is 10 or greater.
+fn generate_snum(rng: &mut impl Rng, depth: usize) -> SNum {
+    if depth == 0 || (depth < 4 && rng.gen_bool(0.6)) {
+        SNum::Pair(Box::new([
+            generate_snum(rng, depth + 1),
+            generate_snum(rng, depth + 1),
+        ]))
+    } else {
+        SNum::Lit(rng.gen_range(0..=9))
+    }
+}
@@ -0,0 +1,355 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{ParseError, Source},
+    geometry::{manhattan, Cuboid, Mat3, Vec3},
+};
+
+pub fn parse(input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    parse_input(&Source::new(19, input), input)
+}
+
+/// Number of beacons in the full map.
+pub fn part1(scanners: &[Scanner]) -> usize {
+    assemble(scanners.to_vec()).0.len()
+}
+
+/// Largest Manhattan distance between any two scanners.
+pub fn part2(scanners: &[Scanner]) -> i64 {
+    let (_, positions) = assemble(scanners.to_vec());
+
+    positions
+        .iter()
+        .tuple_combinations()
+        .map(|(&p1, &p2)| manhattan(p1, p2))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Aligns every scanner with the first one, returning all beacon positions and every
+/// scanner position, relative to the first scanner.
+pub fn assemble(mut scanners: Vec<Scanner>) -> (HashSet<Vec3>, Vec<Vec3>) {
+    let scanner0 = scanners.remove(0);
+
+    let mut ref_beacons = scanner0.beacons.clone();
+    let mut ref_beacon_dists = scanner0.beacon_distances();
+
+    let mut pending = scanners;
+    let mut done = vec![scanner0];
+
+    while !pending.is_empty() {
+        let mut p = Vec::with_capacity(pending.len());
+
+        // constantly draining and replacing pending but oh well
+        for mut s in pending.into_iter() {
+            if s.fit_pos_rotation(&mut ref_beacons, &mut ref_beacon_dists)
+                .is_some()
+            {
+                done.push(s);
+            } else {
+                crate::trace!(19, "no match yet", scanner = s.id);
+                p.push(s);
+            }
+        }
+        pending = p;
+    }
+
+    (ref_beacons, done.into_iter().map(|s| s.pos).collect())
+}
+
+#[derive(Clone)]
+pub struct Scanner {
+    pub id: usize,
+    pub pos: Vec3,
+    pub rotation: Mat3,
+    pub beacons: HashSet<Vec3>,
+}
+
+impl Default for Scanner {
+    fn default() -> Scanner {
+        Scanner {
+            id: 0,
+            pos: Vec3::ZERO,
+            rotation: Mat3::IDENTITY,
+            beacons: HashSet::new(),
+        }
+    }
+}
+
+type BeaconDists = HashMap<i64, HashSet<(Vec3, Vec3)>>;
+impl Scanner {
+    fn beacon_distances(&self) -> BeaconDists {
+        let mut distances: BeaconDists =
+            HashMap::with_capacity(self.beacons.len() * (self.beacons.len() - 1));
+
+        calc_beacon_dists(&self.beacons, &mut distances);
+        distances
+    }
+
+    fn fit_pos_rotation(
+        &mut self,
+        ref_beacons: &mut HashSet<Vec3>,
+        ref_beacon_dists: &mut BeaconDists,
+    ) -> Option<()> {
+        match self.find_pos_rotation(ref_beacons, ref_beacon_dists) {
+            Some((scanner_pos, rot)) => {
+                self.pos = scanner_pos;
+                self.rotation = rot;
+
+                // grow reference set
+                let scanner_pos_rot = self.rotation * self.pos;
+                let inv_rot = self.rotation.transpose(); // a rotation's inverse
+
+                for b in self
+                    .beacons
+                    .iter()
+                    .map(|&pos| inv_rot * (pos + scanner_pos_rot))
+                {
+                    ref_beacons.insert(b);
+                }
+
+                calc_beacon_dists(ref_beacons, ref_beacon_dists);
+                Some(())
+            }
+            None => None,
+        }
+    }
+
+    fn find_pos_rotation(
+        &self,
+        ref_beacons: &HashSet<Vec3>,
+        ref_beacon_dists: &BeaconDists,
+    ) -> Option<(Vec3, Mat3)> {
+        let in_range = Cuboid::new(Vec3::splat(-999), Vec3::splat(999));
+
+        for (from_coord, to_coord) in self.find_beacon_mappings_from(ref_beacon_dists) {
+            // to_coord is obtained from rotation followed by translation
+            // so let's see what combos work
+
+            let possible_transforms = Mat3::orientations().into_iter().map(|rot| {
+                let scanner_pos_rotated = rot * from_coord - to_coord;
+                (rot, scanner_pos_rotated)
+            });
+
+            for (rot, scanner_pos_rotated) in possible_transforms {
+                let ref_beacons_transf: Vec<_> = ref_beacons
+                    .iter()
+                    .map(|&beacon_pos| rot * beacon_pos - scanner_pos_rotated)
+                    .filter(|&b| in_range.contains(b))
+                    .collect();
+
+                // scanners that see at least 12 of the same beacons can be aligned
+                if ref_beacons_transf.len() >= 12
+                    && ref_beacons_transf.iter().all(|b| self.beacons.contains(b))
+                {
+                    let scanner_pos = rot.transpose() * scanner_pos_rotated;
+                    return Some((scanner_pos, rot));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Pairs of a reference beacon and one of ours that could be the same beacon, the most
+    /// likely first.
+    ///
+    /// The same beacon is at the same distances from the other beacons both scanners see,
+    /// so the more distances two beacons have in common, the more likely they match.
+    /// Distances shared by chance are common enough that no single one can rule a pair out.
+    fn find_beacon_mappings_from(
+        &self,
+        from_beacon_dists: &BeaconDists,
+    ) -> impl Iterator<Item = (Vec3, Vec3)> {
+        let to_beacon_dists = self.beacon_distances();
+
+        let mut shared_dists: HashMap<(Vec3, Vec3), usize> = HashMap::new();
+        for (from, to) in from_beacon_dists
+            .iter()
+            .filter(|&(dist, _)| to_beacon_dists.contains_key(dist))
+            .map(|(dist, from)| (from, &to_beacon_dists[dist]))
+        {
+            let from_set: HashSet<Vec3> = from.iter().flat_map(|&(a, b)| [a, b]).collect();
+            let to_set: HashSet<Vec3> = to.iter().flat_map(|&(a, b)| [a, b]).collect();
+
+            for &from in &from_set {
+                for &to in &to_set {
+                    *shared_dists.entry((from, to)).or_default() += 1;
+                }
+            }
+        }
+
+        // a beacon seen by both scanners shares distances with the 11 (or more) other beacons
+        // they both see, while a couple of shared distances are just chance
+        let mut mappings: Vec<_> = shared_dists
+            .into_iter()
+            .filter(|&(_, count)| count >= 3)
+            .collect();
+        mappings.sort_unstable_by(|(pair1, count1), (pair2, count2)| {
+            count2.cmp(count1).then(pair1.cmp(pair2))
+        });
+
+        mappings.into_iter().map(|(pair, _)| pair)
+    }
+}
+
+fn parse_input(src: &Source, input: &str) -> Result<Vec<Scanner>, ParseError> {
+    let mut scanners = Vec::new();
+    let mut current_scanner: Option<(&str, Scanner)> = None;
+
+    for line in input.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with("--- scanner ") {
+            if let Some((header, scanner)) = current_scanner.take() {
+                scanners.push(finish_scanner(src, header, scanner, scanners.len())?);
+            }
+            current_scanner = Some((line, Scanner::default()));
+        } else if let Some((_, scanner)) = &mut current_scanner {
+            let beacon_coords = {
+                let mut it = line
+                    .split(',')
+                    .map(|s| src.parse::<i64>(s, "a beacon coordinate (integer)"));
+                let mut next = || src.require(it.next(), line, "3 comma-separated coordinates")?;
+                let coords = [next()?, next()?, next()?];
+                if it.next().is_some() {
+                    return Err(src.error(line, "3 comma-separated coordinates"));
+                }
+                coords
+            };
+            scanner.beacons.insert(beacon_coords.into());
+        } else {
+            return Err(src.error(line, "a scanner header like `--- scanner 0 ---`"));
+        }
+    }
+
+    match current_scanner {
+        Some((header, scanner)) => {
+            scanners.push(finish_scanner(src, header, scanner, scanners.len())?);
+            Ok(scanners)
+        }
+        None => Err(src.error_at_end("at least one scanner")),
+    }
+}
+
+/// Assigns the next id to a scanner once all its beacons are known.
+fn finish_scanner(
+    src: &Source,
+    header: &str,
+    mut scanner: Scanner,
+    id: usize,
+) -> Result<Scanner, ParseError> {
+    if scanner.beacons.is_empty() {
+        return Err(src.error_after(header, "at least one beacon per scanner"));
+    }
+
+    scanner.id = id;
+    Ok(scanner)
+}
+
+fn calc_beacon_dists(beacons: &HashSet<Vec3>, beacon_dists: &mut BeaconDists) {
+    for (idx1, pos1) in beacons.iter().enumerate() {
+        for pos2 in beacons.iter().skip(idx1 + 1) {
+            let d = manhattan(*pos1, *pos2);
+            //let MAX_DIST: i32 = (3.0 * (2000 as f64).powi(2)).sqrt().ceil() as i32; // should be const :/
+            const MAX_DIST: i64 = 3465;
+
+            if d <= MAX_DIST {
+                beacon_dists.entry(d).or_default().insert((*pos1, *pos2));
+            }
+        }
+    }
+}
+
+/// Random reports of `size` scanners, made up from a hidden map of beacons.
+///
+/// Every scanner (except the first one, which is the origin) is placed near an earlier one
+/// so that at least 12 beacons are in range of both, so the full map can always be
+/// assembled.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    const RANGE: i64 = 1000;
+
+    let size = size.max(1);
+    let rotations = Mat3::orientations();
+
+    let mut positions = vec![Vec3::ZERO];
+    let mut beacons = HashSet::new();
+    for _ in 1..size {
+        let neighbour = *positions.choose(rng).unwrap();
+        let mut pos = Vec3::ZERO;
+        for axis in 0..3 {
+            pos[axis] = neighbour[axis] + rng.gen_range(-1200..=1200);
+        }
+
+        // beacons both scanners can see (not right at the edge of their range)
+        let mut low = Vec3::ZERO;
+        let mut high = Vec3::ZERO;
+        for axis in 0..3 {
+            low[axis] = pos[axis].max(neighbour[axis]) - (RANGE - 1);
+            high[axis] = pos[axis].min(neighbour[axis]) + (RANGE - 1);
+        }
+        let mut shared = HashSet::new();
+        while shared.len() < 12 {
+            shared.insert(random_coord(rng, low, high));
+        }
+
+        beacons.extend(shared);
+        positions.push(pos);
+    }
+    for pos in &positions {
+        let low = *pos - Vec3::splat(RANGE);
+        let high = *pos + Vec3::splat(RANGE);
+        for _ in 0..10 {
+            beacons.insert(random_coord(rng, low, high));
+        }
+    }
+    let mut beacons: Vec<_> = beacons.into_iter().collect();
+    beacons.sort_unstable();
+
+    let mut out = String::new();
+    for (id, pos) in positions.iter().enumerate() {
+        let rotation = if id == 0 {
+            Scanner::default().rotation
+        } else {
+            *rotations.choose(rng).unwrap()
+        };
+
+        let in_range = Cuboid::new(Vec3::splat(-RANGE), Vec3::splat(RANGE));
+        let mut report: Vec<Vec3> = beacons
+            .iter()
+            .map(|&beacon| beacon - *pos)
+            .filter(|&relative| in_range.contains(relative))
+            .map(|relative| rotation * relative)
+            .collect();
+        report.shuffle(rng);
+
+        if id > 0 {
+            out.push('\n');
+        }
+        writeln!(out, "--- scanner {} ---", id).unwrap();
+        for beacon in report {
+            writeln!(out, "{}", beacon).unwrap();
+        }
+    }
+    out
+}
+
+/// Random coordinates between `low` and `high` (inclusive) on every axis.
+fn random_coord(rng: &mut impl Rng, low: Vec3, high: Vec3) -> Vec3 {
+    let mut coord = Vec3::ZERO;
+    for axis in 0..3 {
+        coord[axis] = rng.gen_range(low[axis]..=high[axis]);
+    }
+    coord
+}
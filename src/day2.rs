@@ -0,0 +1,607 @@
+//! The submarine's commands, and the small language they're scripted in: besides the
+//! puzzle's `forward`, `down` and `up` lines, scripts can go `back`, `repeat N { ... }`
+//! blocks of commands, define `macro name { ... }` at the top level and run them by name,
+//! and have `#` comments. Commands end at a newline, a `;` or a block's `}`.
+
+use std::{cmp::Reverse, fmt::Write, io};
+
+use clap::ArgEnum;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{char, digit1, line_ending, multispace1, not_line_ending, space0},
+    combinator::{eof, opt, peek, recognize, value},
+    multi::many0_count,
+    sequence::pair,
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{ParseError, Source},
+    solver::Part,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instr {
+    Forward(i64),
+    /// Forward in reverse.
+    Back(i64),
+    Down(i64),
+    Up(i64),
+}
+
+/// One command of a script.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Stmt {
+    Move(Instr),
+    /// The commands of the block, that many times over.
+    Repeat(u64, Vec<Stmt>),
+    /// The macro with this index in [`Program::macros`].
+    Call(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Macro {
+    pub name: String,
+    pub body: Vec<Stmt>,
+}
+
+/// A parsed script. Macros can only call macros defined before them, so there's no
+/// recursion and running a program always ends.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program {
+    pub macros: Vec<Macro>,
+    pub body: Vec<Stmt>,
+}
+
+impl Program {
+    /// The moves the program makes, in order, expanding repeats and macros as they run
+    /// (so a `repeat 1000000000` block takes no memory).
+    pub fn instrs(&self) -> Instrs<'_> {
+        Instrs {
+            program: self,
+            stack: vec![Frame {
+                body: &self.body,
+                next: 0,
+                repeats: 1,
+            }],
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Program {
+    type Item = &'a Instr;
+    type IntoIter = Instrs<'a>;
+
+    fn into_iter(self) -> Instrs<'a> {
+        self.instrs()
+    }
+}
+
+/// Iterator over the moves of a [`Program`], see [`Program::instrs`].
+#[derive(Clone, Debug)]
+pub struct Instrs<'a> {
+    program: &'a Program,
+    stack: Vec<Frame<'a>>,
+}
+
+/// A block being run: the next command in it, and how many times it's left to run.
+#[derive(Clone, Debug)]
+struct Frame<'a> {
+    body: &'a [Stmt],
+    next: usize,
+    repeats: u64,
+}
+
+impl<'a> Iterator for Instrs<'a> {
+    type Item = &'a Instr;
+
+    fn next(&mut self) -> Option<&'a Instr> {
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(stmt) = frame.body.get(frame.next) else {
+                if frame.repeats > 1 {
+                    frame.repeats -= 1;
+                    frame.next = 0;
+                } else {
+                    self.stack.pop();
+                }
+                continue;
+            };
+            frame.next += 1;
+
+            let (body, repeats) = match stmt {
+                Stmt::Move(instr) => return Some(instr),
+                Stmt::Repeat(n, body) => (body, *n),
+                Stmt::Call(idx) => (&self.program.macros[*idx].body, 1),
+            };
+            if repeats > 0 && !body.is_empty() {
+                self.stack.push(Frame {
+                    body,
+                    next: 0,
+                    repeats,
+                });
+            }
+        }
+    }
+}
+
+/// Where parsing failed and what was expected there.
+#[derive(Debug)]
+struct Expected<'a> {
+    at: &'a str,
+    expected: String,
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Expected<'a> {
+    fn from_error_kind(at: &'a str, _kind: nom::error::ErrorKind) -> Self {
+        Expected {
+            at,
+            expected: "a command".to_string(),
+        }
+    }
+
+    fn append(_at: &'a str, _kind: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+type PResult<'a, T> = IResult<&'a str, T, Expected<'a>>;
+
+/// Error that stops parsing, at `at`.
+fn fail<'a, T>(at: &'a str, expected: impl Into<String>) -> PResult<'a, T> {
+    Err(nom::Err::Failure(Expected {
+        at,
+        expected: expected.into(),
+    }))
+}
+
+/// Runs `parser`, making any error a [`fail`]ure at `s`.
+fn expect<'a, T>(
+    s: &'a str,
+    parser: impl FnOnce(&'a str) -> PResult<'a, T>,
+    expected: impl Into<String>,
+) -> PResult<'a, T> {
+    parser(s).or_else(|_| fail(s, expected))
+}
+
+const KEYWORDS: [&str; 6] = ["forward", "back", "down", "up", "repeat", "macro"];
+
+fn comment(s: &str) -> PResult<'_, ()> {
+    value((), pair(char('#'), not_line_ending))(s)
+}
+
+/// Whitespace, comments and empty commands between commands.
+fn filler(s: &str) -> PResult<'_, ()> {
+    value(
+        (),
+        many0_count(alt((value((), multispace1), comment, value((), char(';'))))),
+    )(s)
+}
+
+/// The end of a command, with maybe a comment before it.
+fn end_of_command(s: &str) -> PResult<'_, ()> {
+    let (rest, _) = pair(space0, opt(comment))(s)?;
+    expect(
+        rest,
+        alt((
+            value((), line_ending),
+            value((), char(';')),
+            value((), peek(char('}'))),
+            value((), eof),
+        )),
+        "end of line",
+    )
+}
+
+fn name(s: &str) -> PResult<'_, &str> {
+    recognize(pair(
+        take_while1(|c: char| c.is_ascii_alphabetic() || c == '_'),
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+    ))(s)
+}
+
+fn number<'a, T: std::str::FromStr>(s: &'a str, expected: &str) -> PResult<'a, T> {
+    let (rest, digits) = preceded_by_space(s, digit1, expected)?;
+    match digits.parse() {
+        Ok(n) => Ok((rest, n)),
+        Err(_) => fail(digits, expected),
+    }
+}
+
+/// Runs `parser` after at least one space, failing with `expected` if either is missing.
+fn preceded_by_space<'a, T>(
+    s: &'a str,
+    parser: impl FnOnce(&'a str) -> PResult<'a, T>,
+    expected: &str,
+) -> PResult<'a, T> {
+    let (rest, spaces) = space0(s)?;
+    if spaces.is_empty() {
+        return fail(rest, expected);
+    }
+    expect(rest, parser, expected)
+}
+
+/// A `{ ... }` block of commands, calling `macros`.
+fn block<'a>(s: &'a str, macros: &[Macro]) -> PResult<'a, Vec<Stmt>> {
+    let (s, _) = space0(s)?;
+    let (mut s, _) = expect(s, char('{'), "`{`")?;
+    let mut body = Vec::new();
+    loop {
+        s = filler(s)?.0;
+        if let Ok((rest, _)) = char::<_, Expected>('}')(s) {
+            return Ok((rest, body));
+        }
+        if s.is_empty() {
+            return fail(s, "a command or `}`");
+        }
+        let (rest, stmt) = stmt(s, macros)?;
+        body.push(stmt);
+        s = rest;
+    }
+}
+
+/// One command (but not a macro definition), calling `macros`.
+fn stmt<'a>(s: &'a str, macros: &[Macro]) -> PResult<'a, Stmt> {
+    let (rest, word) = expect(s, name, "a command")?;
+    let make_instr = match word {
+        "forward" => Instr::Forward,
+        "back" => Instr::Back,
+        "down" => Instr::Down,
+        "up" => Instr::Up,
+        "repeat" => {
+            let (rest, count) = number(rest, "a repeat count")?;
+            let (rest, body) = block(rest, macros)?;
+            let (rest, _) = end_of_command(rest)?;
+            return Ok((rest, Stmt::Repeat(count, body)));
+        }
+        "macro" => return fail(word, "a command (macros are defined at the top level)"),
+        _ => {
+            let idx = match macros.iter().position(|m| m.name == word) {
+                Some(idx) => idx,
+                None => return fail(word, "a command, or a macro defined before"),
+            };
+            let (rest, _) = end_of_command(rest)?;
+            return Ok((rest, Stmt::Call(idx)));
+        }
+    };
+
+    let expected = format!("a displacement after `{}`", word);
+    let (rest, n) = number(rest, &expected)?;
+    let (rest, _) = end_of_command(rest)?;
+    Ok((rest, Stmt::Move(make_instr(n))))
+}
+
+/// `macro name { ... }`, calling the macros defined before it.
+fn macro_def<'a>(s: &'a str, macros: &[Macro]) -> PResult<'a, Macro> {
+    let (s, _) = tag("macro")(s)?;
+    let (rest, name) = preceded_by_space(s, name, "a macro name")?;
+    if KEYWORDS.contains(&name) {
+        return fail(name, "a macro name that isn't a command");
+    }
+    if macros.iter().any(|m| m.name == name) {
+        return fail(name, "a macro name not defined before");
+    }
+    let (rest, body) = block(rest, macros)?;
+    let (rest, _) = end_of_command(rest)?;
+    let name = name.to_string();
+    Ok((rest, Macro { name, body }))
+}
+
+fn program(mut s: &str) -> PResult<'_, Program> {
+    let mut program = Program::default();
+    loop {
+        s = filler(s)?.0;
+        if s.is_empty() {
+            return Ok((s, program));
+        }
+        // `macro` alone would be a call of a macro named `macro`, which can't exist
+        let is_def = s
+            .strip_prefix("macro")
+            .is_some_and(|rest| rest.starts_with([' ', '\t']));
+        s = if is_def {
+            let (rest, def) = macro_def(s, &program.macros)?;
+            program.macros.push(def);
+            rest
+        } else {
+            let (rest, stmt) = stmt(s, &program.macros)?;
+            program.body.push(stmt);
+            rest
+        };
+    }
+}
+
+/// Parses a script, or a puzzle input (which is a script without any of the extras).
+pub fn parse(input: &str) -> Result<Program, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(2, input);
+    match program(input) {
+        Ok((_, program)) => Ok(program),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(src.error(e.at, e.expected)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers only"),
+    }
+}
+
+/// Final horizontal position times depth, with `up`/`down` changing the depth directly.
+pub fn part1(program: &Program) -> i64 {
+    Direct.navigate(program).product()
+}
+
+/// Final horizontal position times depth, with `up`/`down` changing the aim.
+pub fn part2(program: &Program) -> i64 {
+    Aim.navigate(program).product()
+}
+
+/// Where the submarine is, and where it's heading.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub horiz: i64,
+    pub depth: i64,
+    /// Always 0 for navigators that don't use it.
+    pub aim: i64,
+}
+
+impl Position {
+    /// Horizontal position times depth, the puzzle's answer.
+    pub fn product(&self) -> i64 {
+        self.horiz * self.depth
+    }
+}
+
+/// How the submarine interprets commands.
+pub trait Navigator {
+    /// The position after one command.
+    fn step(&self, pos: Position, instr: &Instr) -> Position;
+
+    /// The position after every command, starting from the surface.
+    fn navigate<'a>(&self, instrs: impl IntoIterator<Item = &'a Instr>) -> Position
+    where
+        Self: Sized,
+    {
+        instrs
+            .into_iter()
+            .fold(Position::default(), |pos, instr| self.step(pos, instr))
+    }
+
+    /// The position after every command, keeping them all.
+    fn trajectory<'a>(&self, instrs: impl IntoIterator<Item = &'a Instr>) -> Trajectory
+    where
+        Self: Sized,
+    {
+        let mut positions = vec![Position::default()];
+        for instr in instrs {
+            positions.push(self.step(*positions.last().unwrap(), instr));
+        }
+        Trajectory { positions }
+    }
+}
+
+/// Every position of the submarine along its course: at the surface before step 1, then
+/// after each command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Trajectory {
+    positions: Vec<Position>,
+}
+
+impl Trajectory {
+    /// Positions by step, the start being step 0.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    pub fn end(&self) -> Position {
+        *self.positions.last().unwrap()
+    }
+
+    /// The first step at the deepest point of the course, and its depth.
+    pub fn max_depth(&self) -> (usize, i64) {
+        let (step, pos) = self
+            .positions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, pos)| Reverse(pos.depth))
+            .unwrap();
+        (step, pos.depth)
+    }
+
+    /// Length of the course, going in a straight line from each position to the next.
+    pub fn distance(&self) -> f64 {
+        self.positions
+            .windows(2)
+            .map(|w| {
+                let horiz = (w[1].horiz - w[0].horiz) as f64;
+                let depth = (w[1].depth - w[0].depth) as f64;
+                horiz.hypot(depth)
+            })
+            .sum()
+    }
+
+    /// One `step,horiz,depth,aim` line per position, after a header.
+    pub fn write_csv(&self, out: &mut impl io::Write) -> io::Result<()> {
+        writeln!(out, "step,horiz,depth,aim")?;
+        for (step, pos) in self.positions.iter().enumerate() {
+            writeln!(out, "{},{},{},{}", step, pos.horiz, pos.depth, pos.aim)?;
+        }
+        Ok(())
+    }
+}
+
+/// Part 1's reading of the manual: `up`/`down` change the depth directly.
+#[derive(Clone, Copy, Debug)]
+pub struct Direct;
+
+impl Navigator for Direct {
+    fn step(&self, pos: Position, instr: &Instr) -> Position {
+        match *instr {
+            Instr::Forward(n) => Position {
+                horiz: pos.horiz + n,
+                ..pos
+            },
+            Instr::Back(n) => Position {
+                horiz: pos.horiz - n,
+                ..pos
+            },
+            Instr::Up(n) => Position {
+                depth: pos.depth - n,
+                ..pos
+            },
+            Instr::Down(n) => Position {
+                depth: pos.depth + n,
+                ..pos
+            },
+        }
+    }
+}
+
+/// Part 2's reading: `up`/`down` change the aim, and `forward` also dives by aim times
+/// the distance (which `back` undoes).
+#[derive(Clone, Copy, Debug)]
+pub struct Aim;
+
+impl Navigator for Aim {
+    fn step(&self, pos: Position, instr: &Instr) -> Position {
+        match *instr {
+            Instr::Forward(n) => Position {
+                horiz: pos.horiz + n,
+                depth: pos.depth + pos.aim * n,
+                ..pos
+            },
+            Instr::Back(n) => Position {
+                horiz: pos.horiz - n,
+                depth: pos.depth - pos.aim * n,
+                ..pos
+            },
+            Instr::Up(n) => Position {
+                aim: pos.aim - n,
+                ..pos
+            },
+            Instr::Down(n) => Position {
+                aim: pos.aim + n,
+                ..pos
+            },
+        }
+    }
+}
+
+/// Either navigator, chosen at run time (e.g. from the command line).
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Navigation {
+    Direct,
+    Aim,
+}
+
+impl Navigation {
+    /// The puzzle part that uses this navigator.
+    pub fn part(self) -> Part {
+        match self {
+            Navigation::Direct => Part::One,
+            Navigation::Aim => Part::Two,
+        }
+    }
+}
+
+impl Navigator for Navigation {
+    fn step(&self, pos: Position, instr: &Instr) -> Position {
+        match self {
+            Navigation::Direct => Direct.step(pos, instr),
+            Navigation::Aim => Aim.step(pos, instr),
+        }
+    }
+}
+
+/// Random course with `size` commands.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(1) {
+        let command = ["forward", "down", "up"].choose(rng).unwrap();
+        writeln!(out, "{} {}", command, rng.gen_range(1..=9)).unwrap();
+    }
+    out
+}
+
+#[test]
+fn navigators() {
+    let course = parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n").unwrap();
+    let direct = Position {
+        horiz: 15,
+        depth: 10,
+        aim: 0,
+    };
+    assert_eq!(Direct.navigate(&course), direct);
+    assert_eq!(Navigation::Direct.navigate(&course), direct);
+    let aim = Position {
+        horiz: 15,
+        depth: 60,
+        aim: 10,
+    };
+    assert_eq!(Aim.navigate(&course), aim);
+    assert_eq!(Navigation::Aim.navigate(&course), aim);
+    assert_eq!(Navigation::Aim.part(), Part::Two);
+}
+
+#[test]
+fn script_language() {
+    let script = parse(
+        "# six dives, then back off\n\
+         macro dive { down 2; forward 1 }\n\
+         macro deep {\n  dive\n  dive # twice\n}\n\
+         repeat 3 { deep }\n\
+         back 2\nforward 0\nrepeat 0 { up 1000 }\n",
+    )
+    .unwrap();
+    assert_eq!(script.macros.len(), 2);
+    assert_eq!(script.instrs().count(), 14);
+    assert_eq!(part1(&script), 4 * 12);
+    assert_eq!(part2(&script), 4 * (42 - 12 * 2));
+
+    let error = |script| {
+        let e = parse(script).unwrap_err();
+        (e.line, e.column, e.expected)
+    };
+    assert_eq!(
+        error("repeat 2 {\n  forward 1 up\n}\n"),
+        (2, 13, "end of line".to_string())
+    );
+    assert_eq!(
+        error("dive\nmacro dive { down 1 }\n"),
+        (1, 1, "a command, or a macro defined before".to_string())
+    );
+    assert_eq!(
+        error("repeat 2 { up 1\n"),
+        (2, 1, "a command or `}`".to_string())
+    );
+    assert_eq!(
+        error("forward -3\n"),
+        (1, 9, "a displacement after `forward`".to_string())
+    );
+    assert_eq!(
+        error("macro up { down 1 }\n"),
+        (1, 7, "a macro name that isn't a command".to_string())
+    );
+}
+
+#[test]
+fn trajectory() {
+    let course = parse("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n").unwrap();
+
+    let direct = Direct.trajectory(&course);
+    assert_eq!(direct.positions().len(), 7);
+    assert_eq!(direct.end(), Direct.navigate(&course));
+    assert_eq!(direct.max_depth(), (5, 10));
+    assert_eq!(direct.distance(), 31.0);
+
+    let aim = Aim.trajectory(&course);
+    assert_eq!(aim.max_depth(), (6, 60));
+    assert!((aim.distance() - (5.0 + 1664f64.sqrt() + 404f64.sqrt())).abs() < 1e-9);
+    let mut csv = Vec::new();
+    aim.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert!(csv.starts_with("step,horiz,depth,aim\n0,0,0,0\n1,5,0,0\n"));
+    assert!(csv.ends_with("\n6,15,60,10\n"));
+
+    // an empty course stays at the surface
+    let still = Aim.trajectory(&Program::default());
+    assert_eq!((still.max_depth(), still.distance()), ((0, 0), 0.0));
+}
@@ -0,0 +1,139 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use rand::Rng;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::Grid,
+};
+
+/// The lit pixels and whether they are inverted (i.e. the set holds the dark pixels and
+/// everything else, up to infinity, is lit).
+pub type Image = (HashSet<(isize, isize)>, bool);
+
+pub fn parse(input: &str) -> Result<(Image, Vec<bool>), ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    parse_input(&Source::new(20, input), input)
+}
+
+/// Number of lit pixels after enhancing the image twice.
+pub fn part1((image, algo_map): &(Image, Vec<bool>)) -> usize {
+    enhance_n(image.clone(), algo_map, 2)
+}
+
+/// Number of lit pixels after enhancing the image 50 times.
+pub fn part2((image, algo_map): &(Image, Vec<bool>)) -> usize {
+    enhance_n(image.clone(), algo_map, 50)
+}
+
+fn enhance_n(mut image: Image, algo_map: &[bool], n: usize) -> usize {
+    for _ in 0..n {
+        image = enhance(image, algo_map);
+    }
+
+    assert!(!image.1); // can't be inverted in the end
+    image.0.len()
+}
+
+fn parse_input(src: &Source, input: &str) -> Result<(Image, Vec<bool>), ParseError> {
+    let pixel = |line: &str, idx: usize, c: char| match c {
+        '.' => Ok(false),
+        '#' => Ok(true),
+        _ => Err(src.error(&line[idx..], "`.` or `#`")),
+    };
+
+    let mut lines = crate::input::skip_blank_lines(input).lines();
+    let mut algo_map = Vec::with_capacity(512);
+    for line in &mut lines {
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+
+        for (idx, c) in line.char_indices() {
+            algo_map.push(pixel(line, idx, c)?);
+        }
+    }
+
+    if algo_map.len() != 512 {
+        return Err(src.error(input, "an image enhancement algorithm with 512 pixels"));
+    }
+    if algo_map[0] && algo_map[0b111_111_111] {
+        return Err(src.error(
+            input,
+            "an algorithm that doesn't leave infinitely many pixels lit",
+        ));
+    }
+
+    let mut image = HashSet::new();
+    for (i, line) in lines.skip_while(|line| line.is_empty()).enumerate() {
+        for (j, c) in line.trim_end().char_indices() {
+            if pixel(line, j, c)? {
+                image.insert((i as isize, j as isize));
+            }
+        }
+    }
+
+    Ok(((image, false), algo_map))
+}
+
+pub fn enhance((image, inverted): Image, algo_map: &[bool]) -> Image {
+    let mut new = HashSet::new();
+    let new_inverted = if inverted {
+        algo_map[0b111_111_111]
+    } else {
+        algo_map[0]
+    };
+
+    for (x, y) in image
+        .iter()
+        .flat_map(|&(x, y)| (x - 1..=x + 1).cartesian_product(y - 1..=y + 1))
+    {
+        let n = (x - 1..=x + 1)
+            .cartesian_product(y - 1..=y + 1)
+            .map(|(x, y)| {
+                if image.contains(&(x, y)) != inverted {
+                    1_usize
+                } else {
+                    0
+                }
+            })
+            .fold(0_usize, |acc, x| acc * 2 + x);
+
+        if algo_map[n] != new_inverted {
+            new.insert((x, y));
+        }
+    }
+
+    (new, new_inverted)
+}
+
+/// Draws the part of the image holding the pixels that differ from the infinite
+/// background, one row per line.
+pub fn render_image((image, inverted): &Image) -> String {
+    let (pixels, _) = Grid::from_points(image);
+    pixels
+        .map(|&in_set| if in_set != *inverted { '#' } else { '.' })
+        .to_string()
+}
+
+/// Random enhancement algorithm and `size`x`size` input image. The algorithm never lights
+/// up the infinite dark area for good (the first and last entries aren't both lit).
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let pixel = |lit| if lit { '#' } else { '.' };
+
+    let mut algo: Vec<bool> = (0..512).map(|_| rng.gen()).collect();
+    if algo[0] {
+        algo[511] = false;
+    }
+
+    let mut out: String = algo.into_iter().map(pixel).collect();
+    out.push_str("\n\n");
+    for _ in 0..size.max(1) {
+        out.extend((0..size.max(1)).map(|_| pixel(rng.gen())));
+        out.push('\n');
+    }
+    out
+}
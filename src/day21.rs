@@ -0,0 +1,185 @@
+use itertools::Itertools;
+use rand::Rng;
+
+use crate::error::{ParseError, Source};
+
+const N_PLAYERS: usize = 2;
+const N_POS: usize = 10;
+const MAX_SCORE: usize = 21;
+
+struct Player {
+    pos: u32,
+    score: u32,
+}
+
+// aged like milk \/
+trait Die {
+    fn roll(&mut self) -> u32;
+}
+
+struct DeterministicDie {
+    state: u32,
+    roll_count: usize,
+}
+
+/// Parses both players' starting positions, e.g. `Player 1 starting position: 4`.
+pub fn parse(input: &str) -> Result<[u32; 2], ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(21, input);
+    let mut lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+
+    let mut starting_position = |player| {
+        let line = src.require(
+            lines.next(),
+            input.trim_end(),
+            format!("player {}'s starting position", player),
+        )?;
+        let (_, pos) = src.require(
+            line.rsplit_once(": "),
+            line,
+            "a line like `Player 1 starting position: 4`",
+        )?;
+
+        match src.parse(pos, "a position between 1 and 10")? {
+            pos @ 1..=10 => Ok(pos),
+            _ => Err(src.error(pos, "a position between 1 and 10")),
+        }
+    };
+
+    let starting_positions = [starting_position(1)?, starting_position(2)?];
+    if let Some(extra) = lines.next() {
+        return Err(src.error(extra, "end of input"));
+    }
+
+    Ok(starting_positions)
+}
+
+/// Losing score times number of die rolls in a game with the deterministic die.
+pub fn part1(&[start1, start2]: &[u32; 2]) -> usize {
+    let mut players = [Player::new(start1), Player::new(start2)];
+    let mut det_die = DeterministicDie::default();
+
+    let winner_idx = 'outer: loop {
+        for (idx, p) in players.iter_mut().enumerate() {
+            p.play(&mut det_die);
+
+            if p.score >= 1000 {
+                break 'outer idx;
+            }
+        }
+    };
+
+    let loser = &players[(winner_idx + 1) % 2];
+    loser.score as usize * det_die.roll_count
+}
+
+impl Player {
+    fn new(starting_pos: u32) -> Self {
+        Player {
+            score: 0,
+            pos: starting_pos,
+        }
+    }
+
+    fn play(&mut self, die: &mut dyn Die) {
+        for _ in 0..3 {
+            self.pos = (self.pos - 1 + die.roll()) % 10 + 1;
+        }
+
+        self.score += self.pos;
+    }
+}
+
+impl Die for DeterministicDie {
+    fn roll(&mut self) -> u32 {
+        let n = self.state;
+        self.state = (self.state + 1) % 100;
+
+        self.roll_count += 1;
+
+        n + 1
+    }
+}
+
+#[allow(clippy::derivable_impls)]
+impl Default for DeterministicDie {
+    fn default() -> Self {
+        DeterministicDie {
+            state: 0,
+            roll_count: 0,
+        }
+    }
+}
+
+/// Number of universes in which the player that wins in more universes wins, with the Dirac die.
+pub fn part2(&[start1, start2]: &[u32; 2]) -> u128 {
+    // several megabytes, too big for the stack of anything but the main thread
+    let mut win_counts =
+        vec![vec![vec![[[None; N_POS]; N_POS]; MAX_SCORE + 1]; MAX_SCORE + 1]; N_PLAYERS];
+    // to know counts for (score1, score2, pos1, pos2, next_player)
+    // i must know counts for (score1+dp1, score2+dp2, pos1+r1+r2+r3 % 10, pos2+r4+r5+r6 % 10, (next_player + 1) % 2)
+
+    win_counts[0][MAX_SCORE] = vec![[[Some((1_u128, 0_u128)); N_POS]; N_POS]; MAX_SCORE + 1];
+    for counts in win_counts[1].iter_mut() {
+        counts[MAX_SCORE] = [[Some((0, 1)); N_POS]; N_POS];
+    }
+
+    let states = scores_iter()
+        .cartesian_product(0..N_PLAYERS)
+        .cartesian_product(0..N_POS)
+        .cartesian_product(0..N_POS)
+        .map(|((((s1, s2), p), p1), p2)| (p, s1, s2, p1, p2));
+
+    for (player, score1, score2, pos1, pos2) in states {
+        win_counts[player][score1][score2][pos1][pos2] = dirac_3rolls()
+            .map(|r123| {
+                let mut next_score1 = score1;
+                let mut next_score2 = score2;
+                let mut next_pos1 = pos1;
+                let mut next_pos2 = pos2;
+                let next_player = (player + 1) % N_PLAYERS;
+
+                if next_player == 0 {
+                    next_pos1 = (pos1 + r123) % N_POS;
+                    next_score1 = (score1 + next_pos1 + 1).min(MAX_SCORE);
+                } else {
+                    next_pos2 = (pos2 + r123) % N_POS;
+                    next_score2 = (score2 + next_pos2 + 1).min(MAX_SCORE);
+                }
+
+                win_counts[next_player][next_score1][next_score2][next_pos1][next_pos2].unwrap()
+            })
+            .reduce(|(acc1, acc2), (x1, x2)| (acc1 + x1, acc2 + x2));
+    }
+
+    // player 2 was the last to play, so player 1 goes first
+    let (p1_winner_count, p2_winner_count) =
+        win_counts[1][0][0][start1 as usize - 1][start2 as usize - 1].unwrap();
+    p1_winner_count.max(p2_winner_count)
+}
+
+fn dirac_3rolls() -> impl Iterator<Item = usize> + Clone {
+    (1..=3)
+        .cartesian_product(1..=3)
+        .cartesian_product(1..=3)
+        .map(move |((r1, r2), r3)| r1 + r2 + r3)
+}
+
+fn scores_iter() -> impl Iterator<Item = (usize, usize)> + Clone {
+    let mut scores = (0..MAX_SCORE)
+        .rev()
+        .cartesian_product((0..MAX_SCORE).rev())
+        .collect_vec();
+    scores.sort_unstable_by_key(|(s1, s2)| usize::MAX - *s1 - *s2);
+    scores.into_iter()
+}
+
+/// Random starting positions (there is nothing to scale, so `size` is ignored).
+pub fn generate(rng: &mut impl Rng, _size: usize) -> String {
+    format!(
+        "Player 1 starting position: {}\nPlayer 2 starting position: {}\n",
+        rng.gen_range(1..=10),
+        rng.gen_range(1..=10)
+    )
+}
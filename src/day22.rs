@@ -0,0 +1,184 @@
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+use std::str::FromStr;
+
+use crate::{
+    error::{ParseError, Source},
+    geometry::Vec3,
+};
+
+pub use crate::geometry::Cuboid;
+
+#[derive(Default)]
+pub struct ReactorCore(Vec<Cuboid>);
+
+pub fn parse(input: &str) -> Result<Vec<Instr>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(22, input);
+    input
+        .lines()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_instr(&src, s))
+        .collect()
+}
+
+/// Number of cubes left on in the initialization region (-50..=50 on every axis).
+pub fn part1(instrs: &[Instr]) -> u128 {
+    let region = Cuboid::new(Vec3::splat(-50), Vec3::splat(50));
+    reboot(instrs, &region).on_count()
+}
+
+/// Number of cubes left on after running every reboot step.
+pub fn part2(instrs: &[Instr]) -> u128 {
+    let region = Cuboid::new(Vec3::splat(-i64::MAX), Vec3::splat(i64::MAX));
+    reboot(instrs, &region).on_count()
+}
+
+/// Runs every reboot step, ignoring anything outside `region`.
+pub fn reboot(instrs: &[Instr], region: &Cuboid) -> ReactorCore {
+    let mut core = ReactorCore::default();
+
+    for instr in instrs {
+        crate::trace!(22, "step", instr = instr);
+        match instr {
+            Instr::On(c) => {
+                if let Some(c) = c.intersection(region) {
+                    core.on(c)
+                }
+            }
+            Instr::Off(c) => {
+                if let Some(c) = c.intersection(region) {
+                    core.off(c)
+                }
+            }
+        }
+
+        crate::trace!(
+            22,
+            "core",
+            cuboids = core.0.len(),
+            on = core.on_count(),
+            sorted = {
+                let mut cuboids = core.0.clone();
+                cuboids.sort();
+                cuboids
+            },
+        );
+    }
+
+    core
+}
+
+impl ReactorCore {
+    pub fn on(&mut self, new_c: Cuboid) {
+        let mut new_bits = vec![new_c];
+
+        // only keep the parts of the new cuboid that aren't on yet
+        for c in self.0.iter() {
+            new_bits = new_bits
+                .into_iter()
+                .flat_map(|bit| bit.difference(c))
+                .collect();
+        }
+
+        self.0.extend(new_bits);
+    }
+
+    pub fn off(&mut self, to_remove: Cuboid) {
+        self.0 = std::mem::take(&mut self.0)
+            .into_iter()
+            .flat_map(|c| c.difference(&to_remove))
+            .collect();
+    }
+
+    pub fn on_count(&self) -> u128 {
+        self.0.iter().map(Cuboid::volume).sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Instr {
+    On(Cuboid),
+    Off(Cuboid),
+}
+
+impl FromStr for Instr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_instr(&Source::new(22, s), s)
+    }
+}
+
+fn parse_instr(src: &Source, s: &str) -> Result<Instr, ParseError> {
+    let s = s.trim();
+    let (tag, cuboid) = src.require(s.split_once(' '), s, "a cuboid after `on`/`off`")?;
+
+    let (x, y, z) = match cuboid.split(',').collect::<Vec<_>>()[..] {
+        [x, y, z] => (x, y, z),
+        _ => return Err(src.error(cuboid, "a cuboid like `x=-1..1,y=-2..2,z=-3..3`")),
+    };
+    let cuboid = Cuboid::from_ranges(
+        parse_range(src, x, "x=")?,
+        parse_range(src, y, "y=")?,
+        parse_range(src, z, "z=")?,
+    );
+
+    match tag {
+        "on" => Ok(Instr::On(cuboid)),
+        "off" => Ok(Instr::Off(cuboid)),
+        _ => Err(src.error(tag, "`on` or `off`")),
+    }
+}
+
+fn parse_range(src: &Source, s: &str, axis: &str) -> Result<RangeInclusive<i64>, ParseError> {
+    let range = s
+        .strip_prefix(axis)
+        .ok_or_else(|| src.error(s, format!("`{}`", axis)))?;
+    let (start, end) = src.require(range.split_once(".."), range, "a range like `1..2`")?;
+    let (start, end) = (
+        src.parse::<i64>(start, "an integer")?,
+        src.parse::<i64>(end, "an integer")?,
+    );
+    if start > end {
+        return Err(src.error(range, "a range whose start is not after its end"));
+    }
+
+    Ok(start..=end)
+}
+
+/// Random reboot procedure with `size` steps. Like the puzzle's, the first tenth of them
+/// stay inside the initialization region and the rest are huge cuboids.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    let init_steps = size.div_ceil(10);
+
+    let mut out = String::new();
+    for step in 0..size {
+        let (min, max, len) = if step < init_steps {
+            (-50, 50, 10..=40)
+        } else {
+            (-100_000, 100_000, 10_000..=50_000)
+        };
+
+        let mut ranges = Vec::with_capacity(3);
+        for axis in ["x", "y", "z"] {
+            let len = rng.gen_range(len.clone());
+            let start = rng.gen_range(min..=max - len);
+            ranges.push(format!("{}={}..{}", axis, start, start + len));
+        }
+
+        let on = step == 0 || rng.gen_bool(0.6);
+        writeln!(
+            out,
+            "{} {}",
+            if on { "on" } else { "off" },
+            ranges.join(",")
+        )
+        .unwrap();
+    }
+    out
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    error::{ParseError, Source},
+    solver::NoAnswer,
+};
+
+pub fn parse(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(3, input);
+    let report = input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let line = line.trim();
+            let bits = line
+                .char_indices()
+                .map(|(idx, c)| match c {
+                    '1' => Ok(1),
+                    '0' => Ok(0),
+                    _ => Err(src.error(&line[idx..], "a binary digit")),
+                })
+                .collect::<Result<Vec<u8>, _>>()?;
+            Ok((line, bits))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if report.is_empty() {
+        return Err(src.error_at_end("at least one binary number"));
+    }
+
+    let width = report[0].1.len();
+    if let Some((line, _)) = report.iter().find(|(_, bits)| bits.len() != width) {
+        return Err(src.error(line, format!("a {} bit binary number", width)));
+    }
+
+    // part 2 narrows the report down to a single number, which can't be done with two
+    let mut seen = HashSet::new();
+    if let Some((line, _)) = report.iter().find(|(_, bits)| !seen.insert(bits)) {
+        return Err(src.error(line, "a number not already in the report"));
+    }
+
+    let report = report.into_iter().map(|(_, bits)| bits).collect();
+    Ok(report)
+}
+
+/// Power consumption: gamma rate times epsilon rate.
+pub fn part1(report: &[Vec<u8>]) -> u64 {
+    let mut counts: Vec<isize> = Vec::new();
+
+    for line in report {
+        // ensure counts is big enough
+        counts.resize(line.len(), 0);
+
+        for (idx, &bit) in line.iter().enumerate() {
+            let diff = match bit {
+                1 => 1_isize,
+                0 => -1_isize,
+                _ => unreachable!(),
+            };
+            counts[idx] += diff;
+        }
+    }
+
+    let gamma_bits = |&count| if count > 0 { 1u8 } else { 0 };
+    let epsilon_bits = |&count| if count < 0 { 1u8 } else { 0 };
+    let bit_folder = |acc, bit| (acc << 1) + bit as u64;
+
+    let gamma = counts.iter().map(gamma_bits).fold(0u64, bit_folder);
+
+    let epsilon = counts.iter().map(epsilon_bits).fold(0u64, bit_folder);
+
+    gamma * epsilon
+}
+
+/// Life support rating: oxygen generator rating times CO2 scrubber rating.
+pub fn part2(report: &[Vec<u8>]) -> Result<u64, NoAnswer> {
+    let o2_gen_rating_bit_criteria = |count| match count {
+        1.. => 1,
+        0 => 1,
+        _ => 0,
+    };
+    let co2_scrub_rating_bit_criteria = |count| match count {
+        1.. => 0,
+        0 => 0,
+        _ => 1,
+    };
+
+    let o2_gen_rating = compute_rating(o2_gen_rating_bit_criteria, report.to_vec())
+        .ok_or("no number is left for the oxygen generator rating")?;
+    let co2_scrub_rating = compute_rating(co2_scrub_rating_bit_criteria, report.to_vec())
+        .ok_or("no number is left for the CO2 scrubber rating")?;
+
+    Ok(o2_gen_rating * co2_scrub_rating)
+}
+
+/// `None` if the bit criteria filter every number out (e.g. the CO2 scrubber's, when all
+/// numbers left have the same bit).
+fn compute_rating(bit_criteria: impl Fn(isize) -> u8, mut input: Vec<Vec<u8>>) -> Option<u64> {
+    for idx in 0..input[0].len() {
+        if input.len() <= 1 {
+            break;
+        }
+
+        // compute count
+        let count: isize = input
+            .iter()
+            .map(|bitstring| bitstring[idx])
+            .map(|bit| match bit {
+                1 => 1,
+                0 => -1,
+                _ => unreachable!(),
+            })
+            .sum();
+
+        // filter
+        let bit_to_keep = bit_criteria(count);
+        input.retain(|bitstring| bitstring[idx] == bit_to_keep);
+    }
+
+    // numbers are all different, so at most one is left
+    let number = input.first()?;
+    Some(
+        number
+            .iter()
+            .fold(0u64, |acc, &bit| (acc << 1) + bit as u64),
+    )
+}
+
+/// Random diagnostic report with `size` distinct binary numbers, at least 12 bits wide.
+///
+/// Whenever two or more numbers share a prefix, some of them continue with a 0 and others
+/// with a 1, so the bit criteria of part 2 always narrow the report down to one number.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    let width = (usize::BITS - (size - 1).leading_zeros()).max(12) as usize;
+
+    let mut numbers = Vec::with_capacity(size);
+    generate_numbers(rng, &mut String::new(), size, width, &mut numbers);
+    numbers.shuffle(rng);
+
+    numbers.iter().map(|n| format!("{}\n", n)).collect()
+}
+
+/// Adds `count` numbers starting with `prefix` and `width` more bits to `numbers`.
+fn generate_numbers(
+    rng: &mut impl Rng,
+    prefix: &mut String,
+    count: usize,
+    width: usize,
+    numbers: &mut Vec<String>,
+) {
+    if count == 1 {
+        let suffix: String = (0..width)
+            .map(|_| if rng.gen() { '1' } else { '0' })
+            .collect();
+        numbers.push(format!("{}{}", prefix, suffix));
+        return;
+    }
+
+    // split between both possible next bits, at least one number each
+    let room = 1_usize.checked_shl(width as u32 - 1).unwrap_or(usize::MAX);
+    let zeros = rng.gen_range(count.saturating_sub(room).max(1)..=(count - 1).min(room));
+    for (bit, count) in [('0', zeros), ('1', count - zeros)] {
+        prefix.push(bit);
+        generate_numbers(rng, prefix, count, width - 1, numbers);
+        prefix.pop();
+    }
+}
+
+#[test]
+fn report_errors() {
+    let error = |input| {
+        let e = parse(input).unwrap_err();
+        (e.line, e.column, e.expected)
+    };
+    // the blank line counts
+    assert_eq!(
+        error("101\n\n110\n11\n"),
+        (4, 1, "a 3 bit binary number".to_string())
+    );
+    assert_eq!(
+        error("101\n011\n\n101\n"),
+        (4, 1, "a number not already in the report".to_string())
+    );
+
+    // every number left starts with 1, so the CO2 scrubber keeps none
+    let report = parse("110\n111\n").unwrap();
+    assert!(part2(&report).is_err());
+}
@@ -0,0 +1,190 @@
+use std::fmt::Write;
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::error::{ParseError, Source};
+
+pub type Input = (Vec<u32>, Vec<BingoBoard>);
+
+/// Final score of the first board to win.
+pub fn part1((draw_order, boards): &Input) -> u32 {
+    let winners = play(draw_order, boards.clone());
+
+    let (first_num, first_board) = winners
+        .first()
+        .map(|(drawn_num, winners)| (drawn_num, winners.first().unwrap()))
+        .unwrap();
+    first_num * first_board.unmarked_numbers().sum::<u32>()
+}
+
+/// Final score of the last board to win.
+pub fn part2((draw_order, boards): &Input) -> u32 {
+    let winners = play(draw_order, boards.clone());
+
+    let (last_num, last_board) = winners
+        .last()
+        .map(|(drawn_num, winners)| (drawn_num, winners.first().unwrap()))
+        .unwrap();
+    last_num * last_board.unmarked_numbers().sum::<u32>()
+}
+
+/// Draws numbers until every board has won, returning the winners of each round (in order)
+/// together with the number that made them win.
+fn play(draw_order: &[u32], mut boards: Vec<BingoBoard>) -> Vec<(u32, Vec<BingoBoard>)> {
+    let mut winners = Vec::new();
+
+    for &drawn_number in draw_order {
+        if boards.is_empty() {
+            // fast path
+            break;
+        }
+
+        for b in &mut boards {
+            b.draw(drawn_number);
+        }
+
+        // split winners
+        let (winners_this_round, rest) = boards
+            .into_iter()
+            .partition::<Vec<_>, _>(BingoBoard::is_winner);
+
+        // save winners, remove them from race
+        if !winners_this_round.is_empty() {
+            winners.push((drawn_number, winners_this_round));
+        }
+        boards = rest;
+    }
+
+    winners
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct BingoBoard([[u32; 5]; 5], [[bool; 5]; 5]);
+
+impl BingoBoard {
+    pub fn draw(&mut self, n: u32) {
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, &val) in row.iter().enumerate() {
+                if val == n {
+                    self.1[i][j] = true;
+                }
+            }
+        }
+    }
+
+    pub fn is_winner(&self) -> bool {
+        let row_wins = self.1.iter().any(|row| row.iter().all(|&chosen| chosen));
+
+        let col_wins = self
+            .1
+            .iter()
+            .fold([true; 5], |mut state, row| {
+                // compute AND across all columns at once
+                for (idx, &is_chosen) in row.iter().enumerate() {
+                    state[idx] = state[idx] && is_chosen;
+                }
+
+                state
+            })
+            .iter()
+            .any(|&all_chosen| all_chosen);
+
+        row_wins || col_wins
+    }
+
+    pub fn unmarked_numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.iter().enumerate().map(move |(j, &val)| (i, j, val)))
+            .filter(|(i, j, _)| !self.1[*i][*j])
+            .map(|(_, _, val)| val)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(4, input);
+    let mut lines = crate::input::skip_blank_lines(input).split('\n');
+
+    let draw_order = lines.next().unwrap(); // split always yields at least one item
+    let draw_order: Vec<u32> = draw_order
+        .split(',')
+        .map(|n| src.parse(n.trim(), "a drawn number"))
+        .collect::<Result<_, _>>()?;
+
+    let separator = src.require(lines.next(), input, "an empty line after the draw order")?;
+    if !separator.is_empty() {
+        return Err(src.error(separator, "an empty line after the draw order"));
+    }
+
+    let mut boards = Vec::new();
+    while let Some(board) = parse_board(&src, &mut lines)? {
+        boards.push(board);
+
+        // read separating newline
+        match lines.next() {
+            Some("") | None => {}
+            Some(line) => return Err(src.error(line, "an empty line between boards")),
+        }
+    }
+
+    if boards.is_empty() {
+        return Err(src.error_at_end("at least one board"));
+    }
+
+    Ok((draw_order, boards))
+}
+
+fn parse_board<'a>(
+    src: &Source,
+    lines: &mut impl Iterator<Item = &'a str>,
+) -> Result<Option<BingoBoard>, ParseError> {
+    let mut board = BingoBoard::default();
+
+    for i in 0..5 {
+        let line = if i == 0 {
+            // boards may be separated by more than one empty line
+            match lines.find(|line| !line.is_empty()) {
+                Some(line) => line,
+                None => return Ok(None),
+            }
+        } else {
+            match lines.next() {
+                Some(line) => line,
+                None => return Err(src.error_at_end("5 rows per board")),
+            }
+        };
+
+        let mut nums = line.split_ascii_whitespace();
+        for j in 0..5 {
+            let num_str = src.require(nums.next(), line, "5 numbers per row")?;
+            board.0[i][j] = src.parse(num_str, "a board number")?;
+        }
+        if let Some(extra) = nums.next() {
+            return Err(src.error(extra, "5 numbers per row"));
+        }
+    }
+
+    Ok(Some(board))
+}
+
+/// Random bingo game with `size` boards. Every number from 0 to 99 gets drawn, so every
+/// board wins eventually.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut numbers: Vec<u32> = (0..100).collect();
+    numbers.shuffle(rng);
+    let mut out = numbers.iter().join(",");
+    out.push('\n');
+
+    for _ in 0..size.max(1) {
+        out.push('\n');
+        let board: Vec<_> = numbers.choose_multiple(rng, 25).collect();
+        for row in board.chunks(5) {
+            writeln!(out, "{}", row.iter().map(|n| format!("{:2}", n)).join(" ")).unwrap();
+        }
+    }
+    out
+}
@@ -0,0 +1,173 @@
+use nom::{
+    bytes::complete::tag, character::complete::i32 as i32_parser, combinator::all_consuming,
+    sequence::separated_pair, Finish,
+};
+use rand::{seq::SliceRandom, Rng};
+use std::{collections::HashMap, fmt::Write};
+
+use crate::error::{ParseError, Source};
+
+pub type Coord = (i32, i32);
+pub type Line = (Coord, Coord);
+
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(5, input);
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| parse_line(&src, l))
+        .collect()
+}
+
+/// Number of points covered by at least two horizontal or vertical lines.
+pub fn part1(lines: &[Line]) -> usize {
+    count_overlaps(
+        lines
+            .iter()
+            .filter(|(start, end)| start.0 == end.0 || start.1 == end.1),
+    )
+}
+
+/// Number of points covered by at least two lines, diagonals included.
+pub fn part2(lines: &[Line]) -> usize {
+    count_overlaps(lines.iter())
+}
+
+fn count_overlaps<'a>(lines: impl Iterator<Item = &'a Line>) -> usize {
+    line_counts(lines)
+        .values()
+        .filter(|&&num_lines| num_lines > 1)
+        .count()
+}
+
+/// How many lines cover each point covered by at least one.
+pub fn line_counts<'a>(lines: impl Iterator<Item = &'a Line>) -> HashMap<Coord, usize> {
+    let mut points = HashMap::new();
+    for point in lines.flat_map(|&(start, end)| CoordRange::new(start, end)) {
+        *points.entry(point).or_insert(0_usize) += 1;
+    }
+
+    points
+}
+
+pub struct CoordRange {
+    start: Coord,
+    end: Coord,
+    dx: i32,
+    dy: i32,
+    x_or_y: i32,
+}
+
+impl CoordRange {
+    pub fn new(start: Coord, end: Coord) -> Self {
+        let dx = end.0 - start.0;
+        let dy = end.1 - start.1;
+
+        if (dx.abs() > dy.abs() && start.0 > end.0) || start.1 > end.1 {
+            return CoordRange::new(end, start);
+        }
+
+        let x_or_y = if dx > dy { start.0 } else { start.1 };
+
+        CoordRange {
+            start,
+            end,
+            dx,
+            dy,
+            x_or_y,
+        }
+    }
+}
+
+fn parse_line(src: &Source, input: &str) -> Result<Line, ParseError> {
+    let r: nom::IResult<&str, Line> = all_consuming(separated_pair(
+        separated_pair(i32_parser, tag(","), i32_parser),
+        tag(" -> "),
+        separated_pair(i32_parser, tag(","), i32_parser),
+    ))(input.trim());
+
+    let (_rem, (start, end)) = r
+        .finish()
+        .map_err(|e| src.error(e.input, "a line segment like `x1,y1 -> x2,y2`"))?;
+
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
+        return Err(src.error(
+            input,
+            "a horizontal, vertical or 45 degree diagonal line segment",
+        ));
+    }
+
+    Ok((start, end))
+}
+
+impl Iterator for CoordRange {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Naive line drawing algorithm, applied upside down when |dx| <= |dy| (to avoid sparse lines)
+
+        if self.dx.abs() > self.dy.abs() {
+            let x = self.x_or_y;
+            if x > self.end.0 {
+                None
+            } else {
+                let y = self.start.1 + self.dy * (x - self.start.0) / self.dx;
+
+                self.x_or_y += 1;
+                Some((x, y))
+            }
+        } else {
+            let y = self.x_or_y;
+            if y > self.end.1 {
+                None
+            } else {
+                let x = self.start.0 + self.dx * (y - self.start.1) / self.dy;
+
+                self.x_or_y += 1;
+                Some((x, y))
+            }
+        }
+    }
+}
+
+/// Random list of `size` vent lines on a 1000x1000 grid, horizontal, vertical or diagonal
+/// at 45 degrees.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    const MAX: i32 = 999;
+
+    let mut out = String::new();
+    let mut count = 0;
+    while count < size.max(1) {
+        let (x1, y1) = (rng.gen_range(0..=MAX), rng.gen_range(0..=MAX));
+        let (dx, dy) = *[
+            (1, 0),
+            (0, 1),
+            (1, 1),
+            (1, -1),
+            (-1, 0),
+            (0, -1),
+            (-1, -1),
+            (-1, 1),
+        ]
+        .choose(rng)
+        .unwrap();
+
+        // longest length that stays on the grid
+        let room = |start, d| match d {
+            1 => MAX - start,
+            -1 => start,
+            _ => MAX,
+        };
+        let len = rng.gen_range(1..=300).min(room(x1, dx)).min(room(y1, dy));
+        if len == 0 {
+            continue;
+        }
+
+        writeln!(out, "{},{} -> {},{}", x1, y1, x1 + dx * len, y1 + dy * len).unwrap();
+        count += 1;
+    }
+    out
+}
@@ -0,0 +1,66 @@
+use rand::Rng;
+
+use crate::error::{ParseError, Source};
+
+const N_BABIES_PER_FISH: usize = 1;
+
+pub type FishCounts = [usize; 9];
+
+pub fn parse(input: &str) -> Result<FishCounts, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(6, input);
+    let mut fish_count_per_state = [0_usize; 9];
+
+    for timer in input.split(',') {
+        let timer_str = timer.trim();
+        let timer: usize = src.parse(timer_str, "a timer value between 0 and 8")?;
+        if timer > 8 {
+            return Err(src.error(timer_str, "a timer value between 0 and 8"));
+        }
+
+        fish_count_per_state[timer] += 1;
+    }
+
+    Ok(fish_count_per_state)
+}
+
+/// Number of lanternfish after 80 days.
+pub fn part1(fish_count_per_state: &FishCounts) -> usize {
+    simulate(*fish_count_per_state, 80)
+}
+
+/// Number of lanternfish after 256 days.
+pub fn part2(fish_count_per_state: &FishCounts) -> usize {
+    simulate(*fish_count_per_state, 256)
+}
+
+// Idea, don't track each lanternfish, track how many are in each clock cycle
+pub fn simulate(mut fish_count_per_state: FishCounts, n_iter: usize) -> usize {
+    for _ in 0..n_iter {
+        let mut new_fish_count_per_state = [0_usize; 9];
+
+        // decrement timer
+        for state in (1..=8).rev() {
+            new_fish_count_per_state[state - 1] = fish_count_per_state[state];
+        }
+
+        // reset timer for the pregnant fish
+        new_fish_count_per_state[6] += fish_count_per_state[0];
+
+        // spawn newborns (1 per pregnant fish)
+        new_fish_count_per_state[8] += N_BABIES_PER_FISH * fish_count_per_state[0];
+
+        fish_count_per_state = new_fish_count_per_state;
+    }
+
+    fish_count_per_state.iter().sum::<usize>()
+}
+
+/// Random school of `size` lanternfish, with timers between 1 and 5 like the real ones.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let timers: Vec<String> = (0..size.max(1))
+        .map(|_| rng.gen_range(1..=5).to_string())
+        .collect();
+    timers.join(",") + "\n"
+}
@@ -0,0 +1,76 @@
+use rand::Rng;
+
+use crate::error::{ParseError, Source};
+
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(7, input);
+    input
+        .split(',')
+        .map(|pos| src.parse(pos.trim(), "a crab position (integer)"))
+        .collect()
+}
+
+/// Least fuel needed to align every crab when each step costs 1 fuel.
+pub fn part1(positions: &[i64]) -> i64 {
+    let mut positions = positions.to_vec();
+
+    // compute median
+    positions.sort_unstable();
+    let optimal = positions[positions.len().div_euclid(2)];
+
+    positions.iter().map(|&p| (optimal - p).abs()).sum::<i64>()
+}
+
+/// Least fuel needed to align every crab when the n-th step costs n fuel.
+pub fn part2(positions: &[i64]) -> i64 {
+    // we want to minimize the sum of all fuel costs
+    // which is a sum of parabolas concaved up (leaving target position free)
+    // which is itself a parabola concaved up
+    // sooooo we can walk around it to find the minimum
+
+    let mut target_pos = mean(positions); // not optimal, but close to it
+    let mut target_fuel_usage = fuel_usage(positions, target_pos);
+
+    // try going down
+    while target_pos > 1 && fuel_usage(positions, target_pos - 1) < target_fuel_usage {
+        target_pos -= 1;
+        target_fuel_usage = fuel_usage(positions, target_pos);
+    }
+
+    // try going up
+    while target_pos < i64::MAX && fuel_usage(positions, target_pos + 1) < target_fuel_usage {
+        target_pos += 1;
+        target_fuel_usage = fuel_usage(positions, target_pos);
+    }
+
+    // could possibly be further optimized to use a variable step size
+
+    target_fuel_usage
+}
+
+fn fuel_usage(positions: &[i64], target_pos: i64) -> i64 {
+    positions
+        .iter()
+        .map(|&p| (target_pos - p).abs())
+        // step n costs n fuel
+        // all steps cost <sum numbers from 1 to n> fuel
+        .map(|n_steps| n_steps * (n_steps + 1) / 2)
+        .sum::<i64>()
+}
+
+fn mean(v: &[i64]) -> i64 {
+    let sum = v.iter().sum::<i64>();
+    let count = v.len() as i64;
+
+    (sum + (count / 2)) / count
+}
+
+/// Random positions of `size` crabs, between 0 and 2000.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let positions: Vec<String> = (0..size.max(1))
+        .map(|_| rng.gen_range(0..=2000).to_string())
+        .collect();
+    positions.join(",") + "\n"
+}
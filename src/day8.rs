@@ -0,0 +1,279 @@
+use bitflags::bitflags;
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    fmt::Write,
+    ops::{BitAnd, Shr},
+};
+
+use crate::error::{ParseError, Source};
+
+bitflags! {
+    pub struct SegDisp: u8 {
+        const TOP          = 0b00000001; // a
+        const TOP_LEFT     = 0b00000010; // b
+        const TOP_RIGHT    = 0b00000100; // c
+        const MIDDLE       = 0b00001000; // d
+        const BOTTOM_LEFT  = 0b00010000; // e
+        const BOTTOM_RIGHT = 0b00100000; // f
+        const BOTTOM       = 0b01000000; // g
+
+        const ZERO = Self::TOP.bits | Self::TOP_LEFT.bits | Self::TOP_RIGHT.bits | Self::BOTTOM_LEFT.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+
+        const ONE = Self::TOP_RIGHT.bits | Self::BOTTOM_RIGHT.bits;
+
+        const TWO = Self::TOP.bits | Self::TOP_RIGHT.bits | Self::MIDDLE.bits | Self::BOTTOM_LEFT.bits | Self::BOTTOM.bits;
+
+        const THREE = Self::TOP.bits | Self::TOP_RIGHT.bits | Self::MIDDLE.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+
+        const FOUR = Self::TOP_LEFT.bits | Self::TOP_RIGHT.bits | Self::MIDDLE.bits | Self::BOTTOM_RIGHT.bits;
+
+        const FIVE = Self::TOP.bits | Self::TOP_LEFT.bits | Self::MIDDLE.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+
+        const SIX = Self::TOP.bits | Self::TOP_LEFT.bits | Self::MIDDLE.bits | Self::BOTTOM_LEFT.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+
+        const SEVEN = Self::TOP.bits | Self::TOP_RIGHT.bits | Self::BOTTOM_RIGHT.bits;
+
+        const EIGHT = Self::TOP.bits | Self::TOP_LEFT.bits | Self::TOP_RIGHT.bits | Self::MIDDLE.bits | Self::BOTTOM_LEFT.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+
+        const NINE = Self::TOP.bits | Self::TOP_LEFT.bits | Self::TOP_RIGHT.bits | Self::MIDDLE.bits | Self::BOTTOM_RIGHT.bits | Self::BOTTOM.bits;
+    }
+}
+
+const NUMBERS: [SegDisp; 10] = [
+    SegDisp::ZERO,
+    SegDisp::ONE,
+    SegDisp::TWO,
+    SegDisp::THREE,
+    SegDisp::FOUR,
+    SegDisp::FIVE,
+    SegDisp::SIX,
+    SegDisp::SEVEN,
+    SegDisp::EIGHT,
+    SegDisp::NINE,
+];
+
+const SEGMENTS: [SegDisp; 7] = [
+    SegDisp::TOP,
+    SegDisp::TOP_LEFT,
+    SegDisp::TOP_RIGHT,
+    SegDisp::MIDDLE,
+    SegDisp::BOTTOM_LEFT,
+    SegDisp::BOTTOM_RIGHT,
+    SegDisp::BOTTOM,
+];
+type WiringPermutation = [SegDisp; 7];
+
+/// One display: the ten unique signal patterns and the four-digit output value.
+pub type Entry = (Vec<SegDisp>, Vec<SegDisp>);
+
+pub fn parse(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    let src = Source::new(8, input);
+    input
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|line| {
+            let (training, evaluation) = src.require(
+                line.split_once('|'),
+                line,
+                "`|` between the signal patterns and the output value",
+            )?;
+            let training = parse_disps(&src, training, 10)?;
+            let evaluation = parse_disps(&src, evaluation, 4)?;
+            Ok((training, evaluation))
+        })
+        .collect()
+}
+
+/// Number of times the digits 1, 4, 7 or 8 appear in the output values.
+pub fn part1(input: &[Entry]) -> usize {
+    input
+        .iter()
+        .map(|(_training, eval)| eval.iter().filter(|&&s| confusion_score(s) == 1).count())
+        .sum::<usize>()
+}
+
+/// Sum of all decoded output values.
+pub fn part2(input: &[Entry]) -> usize {
+    let mut sum_of_all = 0;
+    for (training_set, eval_set) in input {
+        let permutation = find_inv_permutation(training_set);
+        let number = eval_set
+            .iter()
+            .map(|&disp| apply_permutation(disp, permutation))
+            .map(|disp| NUMBERS.iter().position(|&el| el == disp).unwrap())
+            .fold(0, |state, x| state * 10 + x);
+
+        sum_of_all += number;
+    }
+
+    sum_of_all
+}
+
+fn find_inv_permutation(training_set: &[SegDisp]) -> WiringPermutation {
+    let mut possible_perms: [SegDisp; 7] = [SegDisp::EIGHT; 7];
+
+    for &display in training_set {
+        // get all the segments that should have been on for all number possibilities
+        let possible = NUMBERS
+            .iter()
+            .copied()
+            .filter(|&d| bit_count(d) == bit_count(display))
+            .fold(SegDisp::empty(), SegDisp::union);
+
+        let mangled_seg_indices: Vec<_> = iter_bits(display)
+            .map(|b| SEGMENTS.iter().position(|&b2| b2 == b).unwrap())
+            .collect();
+
+        for &idx in &mangled_seg_indices {
+            possible_perms[idx] = possible_perms[idx].intersection(possible);
+        }
+    }
+
+    possible_perms
+        .iter()
+        .map(|&seg_poss| iter_bits(seg_poss).collect::<Vec<_>>())
+        .multi_cartesian_product()
+        .map(|v| v.try_into().unwrap())
+        .filter(|&perm: &WiringPermutation| {
+            // permutation must be a permutation (no two wires connected to the same thing)
+            let mut conn_count = [0; 7];
+            for idx in perm
+                .iter()
+                .copied()
+                .flat_map(iter_bits)
+                .map(|b| SEGMENTS.iter().position(|&b2| b == b2).unwrap())
+            {
+                conn_count[idx] += 1;
+            }
+
+            conn_count.into_iter().all(|c| c == 1)
+        })
+        .find(|&perm| {
+            // only consider possibilities that actually work
+            training_set
+                .iter()
+                .map(|&disp| apply_permutation(disp, perm))
+                .all(|disp| NUMBERS.contains(&disp))
+        })
+        .unwrap()
+}
+
+fn iter_bits(disp: SegDisp) -> impl Iterator<Item = SegDisp> {
+    SEGMENTS
+        .iter()
+        .copied()
+        .filter(move |&seg| disp.contains(seg))
+}
+
+fn apply_permutation(disp: SegDisp, perm: WiringPermutation) -> SegDisp {
+    let mut applied = SegDisp::empty();
+
+    for seg_idx in iter_bits(disp).map(|bit| SEGMENTS.iter().position(|&b| b == bit).unwrap()) {
+        applied = applied.union(perm[seg_idx]);
+    }
+
+    applied
+}
+
+fn confusion_score(number: SegDisp) -> u8 {
+    // 0, 6, 9 (3 numbers) use 6 segments
+    // 1 (1 number) uses 2 segments
+    // 2, 3, 5 (3 numbers) use 5 segments
+    // 4 (1 number) uses 4 segments
+    // 7 (1 number) uses 3 segments
+    // 8 (1 number) uses 7 segments
+
+    match bit_count(number) {
+        2 | 3 | 4 | 7 => 1,
+        5 | 6 => 3,
+        _ => u8::MAX,
+    }
+}
+
+fn bit_count(number: SegDisp) -> usize {
+    let mut bits = number.bits();
+    let mut count = 0;
+    while bits != 0 {
+        if bits.bitand(0b1) == 0b1 {
+            count += 1;
+        }
+
+        bits = bits.shr(1);
+    }
+
+    count
+}
+
+fn parse_disps(src: &Source, input: &str, count: usize) -> Result<Vec<SegDisp>, ParseError> {
+    let disps = input
+        .split(' ')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| parse_disp(src, s))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if disps.len() != count {
+        return Err(src.error(input, format!("{} digit patterns", count)));
+    }
+
+    Ok(disps)
+}
+
+fn parse_disp(src: &Source, input: &str) -> Result<SegDisp, ParseError> {
+    input
+        .char_indices()
+        .map(|(idx, c)| match c {
+            'a' => Ok(SegDisp::TOP),
+            'b' => Ok(SegDisp::TOP_LEFT),
+            'c' => Ok(SegDisp::TOP_RIGHT),
+            'd' => Ok(SegDisp::MIDDLE),
+            'e' => Ok(SegDisp::BOTTOM_LEFT),
+            'f' => Ok(SegDisp::BOTTOM_RIGHT),
+            'g' => Ok(SegDisp::BOTTOM),
+            _ => Err(src.error(
+                &input[idx..],
+                "segment identifiers are letters between a and g",
+            )),
+        })
+        .try_fold(SegDisp::empty(), |disp, x| Ok(disp.union(x?)))
+}
+
+/// Random notes for `size` displays, each one wired differently.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size.max(1) {
+        // wire segment `SEGMENTS[i]` to letter `wiring[i]`
+        let mut wiring: Vec<char> = ('a'..='g').collect();
+        wiring.shuffle(rng);
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        digits.shuffle(rng);
+        let patterns = digits
+            .iter()
+            .map(|&d| scrambled_digit(rng, &wiring, d))
+            .join(" ");
+        let output = (0..4)
+            .map(|_| {
+                let digit = rng.gen_range(0..10);
+                scrambled_digit(rng, &wiring, digit)
+            })
+            .join(" ");
+
+        writeln!(out, "{} | {}", patterns, output).unwrap();
+    }
+    out
+}
+
+fn scrambled_digit(rng: &mut impl Rng, wiring: &[char], digit: usize) -> String {
+    let mut letters: Vec<char> = SEGMENTS
+        .iter()
+        .zip(wiring)
+        .filter(|(&segment, _)| NUMBERS[digit].contains(segment))
+        .map(|(_, &letter)| letter)
+        .collect();
+    letters.shuffle(rng);
+    letters.into_iter().collect()
+}
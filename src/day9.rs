@@ -0,0 +1,162 @@
+use std::collections::{HashSet, VecDeque};
+
+use rand::Rng;
+
+use crate::{
+    error::{ParseError, Source},
+    grid::{Grid, Pos},
+};
+
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    let input = crate::input::normalize(input);
+    let input = input.as_ref();
+    Grid::parse_digits(&Source::new(9, input), input, 0..=9, "height")
+}
+
+/// Sum of the risk levels of all low points.
+pub fn part1(map: &Grid<u8>) -> u64 {
+    map.enumerate()
+        .filter(|&(pos, _v)| is_low_point(map, pos))
+        .map(|(_pos, &v)| v as u64 + 1) // calc risk level
+        .sum::<u64>()
+}
+
+/// Product of the sizes of the three largest basins.
+pub fn part2(map: &Grid<u8>) -> u64 {
+    let (mut basin_map, next_id) = basin_seeds(map);
+
+    // let basins grow from low points
+    while grow_basins(map, &mut basin_map) {}
+
+    let mut basin_sizes =
+        basin_map
+            .iter()
+            .copied()
+            .fold(vec![0_u64; (next_id + 1) as usize], |mut counts, id| {
+                if id <= next_id {
+                    counts[id as usize] += 1;
+                }
+
+                counts
+            });
+    basin_sizes.sort_unstable();
+
+    basin_sizes.iter().rev().take(3).product::<u64>()
+}
+
+/// Basin IDs before growing: every low point gets its own ID (0, 1...), high points get
+/// `u32::MAX` (not part of any basin) and everything else 0. Also returns the number of
+/// basins.
+pub fn basin_seeds(map: &Grid<u8>) -> (Grid<u32>, u32) {
+    let mut next_id: u32 = 0;
+    let mut basin_map = map.map(|_| next_id);
+
+    // give every basin an ID
+    for pos in map.positions().filter(|&pos| is_low_point(map, pos)) {
+        basin_map[pos] = next_id;
+        next_id += 1;
+    }
+    // mark all high points as non-basins
+    for (pos, _v) in map.enumerate().filter(|&(_, &v)| v == 9) {
+        basin_map[pos] = u32::MAX;
+    }
+
+    (basin_map, next_id)
+}
+
+/// One pass of letting basins spread to their neighbours. Returns false once basins don't
+/// change anymore.
+pub fn grow_basins(map: &Grid<u8>, basin_map: &mut Grid<u32>) -> bool {
+    let mut was_modified = false;
+
+    for pos in map.positions() {
+        let basin_id = basin_map[pos];
+        if basin_id == u32::MAX {
+            continue;
+        }
+
+        for adj in map.neighbours4(pos) {
+            if basin_map[adj] < basin_id {
+                // grow basins deterministically
+                // even if there are overlaps, they will merge properly
+                basin_map[adj] = basin_id;
+                was_modified = true;
+            }
+        }
+    }
+
+    was_modified
+}
+
+fn is_low_point(map: &Grid<u8>, pos: Pos) -> bool {
+    let point_value = map[pos];
+    map.neighbours4(pos)
+        .map(|adj| map[adj])
+        .all(|v| v > point_value)
+}
+
+/// Random `size`x`size` height map made of basins like the puzzle's: each one is walled
+/// off by 9s and slopes down to a single low point.
+pub fn generate(rng: &mut impl Rng, size: usize) -> String {
+    let size = size.max(1);
+    let low_points: Vec<Pos> = (0..(size * size / 40).max(1))
+        .map(|_| (rng.gen_range(0..size), rng.gen_range(0..size)))
+        .collect();
+
+    // every location belongs to the basin of the closest low point, and borders are walls
+    let closest = |(l, c): Pos| {
+        (0..low_points.len())
+            .min_by_key(|&idx| {
+                let (low_l, low_c) = low_points[idx];
+                l.abs_diff(low_l) + c.abs_diff(low_c)
+            })
+            .unwrap()
+    };
+    let basins = Grid::from_fn(size, size, closest);
+
+    let mut heights = Grid::new(size, size, 9_u8);
+    let is_wall = |pos| {
+        basins
+            .neighbours4(pos)
+            .any(|adj| basins[adj] != basins[pos])
+    };
+
+    // heights grow (give or take) with the distance from the low point
+    let mut queue: VecDeque<_> = low_points
+        .iter()
+        .filter(|&&pos| !is_wall(pos))
+        .map(|&pos| (pos, 0))
+        .collect();
+    let mut seen: HashSet<_> = queue.iter().map(|&(pos, _)| pos).collect();
+    while let Some((pos, dist)) = queue.pop_front() {
+        heights[pos] = if dist == 0 {
+            0
+        } else {
+            (dist + rng.gen_range(0..=1)).min(8)
+        };
+
+        for next in basins.neighbours4(pos) {
+            if !is_wall(next) && seen.insert(next) {
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    heights.to_string()
+}
+
+/// A `size`x`size` grid of digits, one row per line.
+pub(crate) fn digit_grid<R: Rng>(
+    rng: &mut R,
+    size: usize,
+    mut digit: impl FnMut(&mut R) -> u8,
+) -> String {
+    let mut out = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push((b'0' + digit(rng)) as char);
+        }
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,104 @@
+//! Errors reported while parsing puzzle inputs.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Bad puzzle input: where it happened and what was expected there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in characters.
+    pub column: usize,
+    pub expected: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// One day's puzzle input, used to turn positions inside it into [`ParseError`]s.
+///
+/// Parsers only ever look at subslices of the original input (lines, tokens, leftovers from
+/// nom), so the position of an error can be recovered from the slice where parsing failed.
+#[derive(Clone, Copy, Debug)]
+pub struct Source<'a> {
+    day: u8,
+    input: &'a str,
+}
+
+impl<'a> Source<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Source { day, input }
+    }
+
+    /// Error at the start of `at`, which must be a subslice of the input (anything else is
+    /// reported at the end of the input).
+    pub fn error(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(self.input.as_ptr() as usize)
+            .filter(|&offset| offset <= self.input.len())
+            .unwrap_or(self.input.len());
+
+        let before = &self.input[..offset];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+
+        ParseError {
+            day: self.day,
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    /// Error right after the end of `at` (e.g. for missing tokens at the end of a line).
+    pub fn error_after(&self, at: &str, expected: impl Into<String>) -> ParseError {
+        self.error(&at[at.len()..], expected)
+    }
+
+    /// Error at the very end of the input.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error_after(self.input, expected)
+    }
+
+    /// Parses `s` with [`FromStr`], reporting failures at `s`.
+    pub fn parse<T: FromStr>(&self, s: &str, expected: impl Into<String>) -> Result<T, ParseError> {
+        s.parse().map_err(|_| self.error(s, expected))
+    }
+
+    /// Unwraps `value`, reporting a missing value right after `at`.
+    pub fn require<T>(
+        &self,
+        value: Option<T>,
+        at: &str,
+        expected: impl Into<String>,
+    ) -> Result<T, ParseError> {
+        value.ok_or_else(|| self.error_after(at, expected))
+    }
+}
+
+#[test]
+fn error_position() {
+    let input = "abc\nde\u{e9}fg\n";
+    let src = Source::new(1, input);
+
+    let e = src.error(&input[8..], "something");
+    assert_eq!((e.line, e.column), (2, 4));
+
+    let e = src.error_after(&input[..3], "something");
+    assert_eq!((e.line, e.column), (1, 4));
+
+    let e = src.error("not in the input", "something");
+    assert_eq!((e.line, e.column), (3, 1));
+}
@@ -0,0 +1,42 @@
+//! Random puzzle inputs, to stress test and benchmark the solvers beyond our own puzzle
+//! inputs.
+//!
+//! Every day has a `generate` function producing input its `parse` accepts, from a random
+//! number generator and a size whose meaning depends on the day (number of lines, side of a
+//! grid, number of scanners...).
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::*;
+
+/// Random input for `day`, always the same for the same `size` and `seed`. `None` for
+/// days that don't exist.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let rng = &mut StdRng::seed_from_u64(seed);
+    let input = match day {
+        1 => day1::generate(rng, size),
+        2 => day2::generate(rng, size),
+        3 => day3::generate(rng, size),
+        4 => day4::generate(rng, size),
+        5 => day5::generate(rng, size),
+        6 => day6::generate(rng, size),
+        7 => day7::generate(rng, size),
+        8 => day8::generate(rng, size),
+        9 => day9::generate(rng, size),
+        10 => day10::generate(rng, size),
+        11 => day11::generate(rng, size),
+        12 => day12::generate(rng, size),
+        13 => day13::generate(rng, size),
+        14 => day14::generate(rng, size),
+        15 => day15::generate(rng, size),
+        16 => day16::generate(rng, size),
+        17 => day17::generate(rng, size),
+        18 => day18::generate(rng, size),
+        19 => day19::generate(rng, size),
+        20 => day20::generate(rng, size),
+        21 => day21::generate(rng, size),
+        22 => day22::generate(rng, size),
+        _ => return None,
+    };
+    Some(input)
+}
@@ -0,0 +1,397 @@
+//! 3D integer geometry: points and vectors, rotation matrices and axis-aligned boxes.
+
+use std::{
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, RangeInclusive, Sub, SubAssign},
+};
+
+/// A point, or the vector between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    /// The same value on every axis.
+    pub const fn splat(v: i64) -> Self {
+        Vec3::new(v, v, v)
+    }
+
+    pub const fn dot(self, other: Vec3) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The sum of the absolute coordinates, i.e. the Manhattan distance from the origin.
+    pub const fn manhattan_len(self) -> i64 {
+        self.x.abs() + self.y.abs() + self.z.abs()
+    }
+}
+
+/// Distance between two points moving along the axes only.
+pub const fn manhattan(a: Vec3, b: Vec3) -> i64 {
+    Vec3::new(a.x - b.x, a.y - b.y, a.z - b.z).manhattan_len()
+}
+
+/// Straight-line distance between two points.
+pub fn euclidean(a: Vec3, b: Vec3) -> f64 {
+    let d = a - b;
+    (d.dot(d) as f64).sqrt()
+}
+
+impl From<[i64; 3]> for Vec3 {
+    fn from([x, y, z]: [i64; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+/// Axis 0 is x, 1 is y and 2 is z.
+impl Index<usize> for Vec3 {
+    type Output = i64;
+
+    fn index(&self, axis: usize) -> &i64 {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("no axis {} in 3D", axis),
+        }
+    }
+}
+
+impl IndexMut<usize> for Vec3 {
+    fn index_mut(&mut self, axis: usize) -> &mut i64 {
+        match axis {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("no axis {} in 3D", axis),
+        }
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, k: i64) -> Vec3 {
+        Vec3::new(self.x * k, self.y * k, self.z * k)
+    }
+}
+
+/// `x,y,z`, the way the puzzles write coordinates.
+impl Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A 3×3 integer matrix, as rows. Mostly used for rotations: `rotation * vector` rotates
+/// the vector, and `a * b` rotates by `b` then by `a`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mat3(pub [[i64; 3]; 3]);
+
+impl Mat3 {
+    pub const IDENTITY: Mat3 = Mat3([[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+
+    /// Quarter turns counterclockwise around the x axis (looking from positive x).
+    pub fn rotation_x(quarter_turns: u32) -> Self {
+        let (sin, cos) = quarter_sin_cos(quarter_turns);
+        Mat3([[1, 0, 0], [0, cos, -sin], [0, sin, cos]])
+    }
+
+    /// Quarter turns counterclockwise around the y axis (looking from positive y).
+    pub fn rotation_y(quarter_turns: u32) -> Self {
+        let (sin, cos) = quarter_sin_cos(quarter_turns);
+        Mat3([[cos, 0, sin], [0, 1, 0], [-sin, 0, cos]])
+    }
+
+    /// Quarter turns counterclockwise around the z axis (looking from positive z).
+    pub fn rotation_z(quarter_turns: u32) -> Self {
+        let (sin, cos) = quarter_sin_cos(quarter_turns);
+        Mat3([[cos, -sin, 0], [sin, cos, 0], [0, 0, 1]])
+    }
+
+    /// The 24 ways to turn something lined up with the axes (which way it faces, times
+    /// which way is up), in order. They are the matrices with one ±1 per row and column
+    /// and a determinant of 1.
+    pub fn orientations() -> [Mat3; 24] {
+        let mut orientations = [Mat3::IDENTITY; 24];
+        let mut n = 0;
+        for axes in [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ] {
+            for signs in 0..8 {
+                let mut m = [[0; 3]; 3];
+                for (row, &axis) in axes.iter().enumerate() {
+                    m[row][axis] = if signs & 1 << row == 0 { 1 } else { -1 };
+                }
+                if Mat3(m).determinant() == 1 {
+                    orientations[n] = Mat3(m);
+                    n += 1;
+                }
+            }
+        }
+        orientations.sort_unstable();
+        orientations
+    }
+
+    pub const fn determinant(&self) -> i64 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[2][1] * m[1][2])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = &self.0;
+        Mat3([
+            [m[0][0], m[1][0], m[2][0]],
+            [m[0][1], m[1][1], m[2][1]],
+            [m[0][2], m[1][2], m[2][2]],
+        ])
+    }
+
+    /// The matrix undoing this one, if it has integer coefficients (always the case for
+    /// rotations, whose inverse is their transpose).
+    pub fn inverse(&self) -> Option<Self> {
+        let d = self.determinant();
+        let m = &self.0;
+        let adjugate = [
+            [
+                m[1][1] * m[2][2] - m[2][1] * m[1][2],
+                m[0][2] * m[2][1] - m[0][1] * m[2][2],
+                m[0][1] * m[1][2] - m[0][2] * m[1][1],
+            ],
+            [
+                m[1][2] * m[2][0] - m[1][0] * m[2][2],
+                m[0][0] * m[2][2] - m[0][2] * m[2][0],
+                m[1][0] * m[0][2] - m[0][0] * m[1][2],
+            ],
+            [
+                m[1][0] * m[2][1] - m[2][0] * m[1][1],
+                m[2][0] * m[0][1] - m[0][0] * m[2][1],
+                m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            ],
+        ];
+
+        if d == 0 || adjugate.iter().flatten().any(|c| c % d != 0) {
+            return None;
+        }
+        Some(Mat3(adjugate.map(|row| row.map(|c| c / d))))
+    }
+}
+
+fn quarter_sin_cos(quarter_turns: u32) -> (i64, i64) {
+    match quarter_turns % 4 {
+        0 => (0, 1),
+        1 => (1, 0),
+        2 => (0, -1),
+        _ => (-1, 0),
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, v: Vec3) -> Vec3 {
+        let [x, y, z] = self.0.map(|row| Vec3::from(row).dot(v));
+        Vec3::new(x, y, z)
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Mat3;
+
+    fn mul(self, other: Mat3) -> Mat3 {
+        let other = other.transpose();
+        Mat3(
+            self.0
+                .map(|row| other.0.map(|column| Vec3::from(row).dot(column.into()))),
+        )
+    }
+}
+
+/// An axis-aligned box of whole cubes, from `min` to `max` included on every axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cuboid {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Cuboid {
+    /// `min` must not be after `max` on any axis.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        debug_assert!(
+            min.x <= max.x && min.y <= max.y && min.z <= max.z,
+            "empty cuboid from {} to {}",
+            min,
+            max
+        );
+        Cuboid { min, max }
+    }
+
+    /// The cuboid covering the ranges on the x, y and z axes.
+    pub fn from_ranges(
+        x: RangeInclusive<i64>,
+        y: RangeInclusive<i64>,
+        z: RangeInclusive<i64>,
+    ) -> Self {
+        Cuboid::new(
+            Vec3::new(*x.start(), *y.start(), *z.start()),
+            Vec3::new(*x.end(), *y.end(), *z.end()),
+        )
+    }
+
+    /// The cuboid's extent on one axis (0 for x, 1 for y, 2 for z).
+    pub fn range(&self, axis: usize) -> RangeInclusive<i64> {
+        self.min[axis]..=self.max[axis]
+    }
+
+    /// Number of cubes inside. Overflows for boxes spanning most of the `i64` range.
+    pub fn volume(&self) -> u128 {
+        (0..3)
+            .map(|axis| (self.max[axis] as i128 - self.min[axis] as i128 + 1) as u128)
+            .product()
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        (0..3).all(|axis| self.range(axis).contains(&point[axis]))
+    }
+
+    /// The cubes in both cuboids, if any.
+    pub fn intersection(&self, other: &Cuboid) -> Option<Cuboid> {
+        let mut min = Vec3::ZERO;
+        let mut max = Vec3::ZERO;
+        for axis in 0..3 {
+            min[axis] = self.min[axis].max(other.min[axis]);
+            max[axis] = self.max[axis].min(other.max[axis]);
+            if min[axis] > max[axis] {
+                return None;
+            }
+        }
+        Some(Cuboid::new(min, max))
+    }
+
+    /// The cubes in this cuboid but not in `other`, as at most 6 disjoint cuboids: slabs
+    /// before and after `other` along x, then along y (within `other`'s x range), then
+    /// along z (within its x and y ranges).
+    pub fn difference(&self, other: &Cuboid) -> impl Iterator<Item = Cuboid> {
+        let mut pieces = [None; 6];
+        match self.intersection(other) {
+            None => pieces[0] = Some(*self),
+            Some(common) => {
+                // what's left to cut, narrowed down to the intersection one axis at a time
+                let mut rest = *self;
+                for axis in 0..3 {
+                    if rest.min[axis] < common.min[axis] {
+                        let mut before = rest;
+                        before.max[axis] = common.min[axis] - 1;
+                        pieces[2 * axis] = Some(before);
+                    }
+                    if rest.max[axis] > common.max[axis] {
+                        let mut after = rest;
+                        after.min[axis] = common.max[axis] + 1;
+                        pieces[2 * axis + 1] = Some(after);
+                    }
+                    rest.min[axis] = common.min[axis];
+                    rest.max[axis] = common.max[axis];
+                }
+            }
+        }
+        pieces.into_iter().flatten()
+    }
+}
+
+#[test]
+fn rotations() {
+    let orientations = Mat3::orientations();
+    let v = Vec3::new(4, 5, 6);
+
+    for (i, &r) in orientations.iter().enumerate() {
+        assert!(!orientations[..i].contains(&r));
+        assert_eq!(r.inverse(), Some(r.transpose()));
+        assert_eq!(r.inverse().unwrap() * (r * v), v);
+        for &s in &orientations {
+            assert!(orientations.contains(&(r * s)));
+            assert_eq!((r * s) * v, r * (s * v));
+        }
+    }
+
+    assert_eq!(Mat3::rotation_z(1) * Vec3::new(1, 0, 0), Vec3::new(0, 1, 0));
+    assert_eq!(Mat3::rotation_x(1) * Mat3::rotation_x(3), Mat3::IDENTITY);
+    assert_eq!(Mat3([[2, 0, 0], [0, 1, 0], [0, 0, 1]]).inverse(), None);
+    assert_eq!(manhattan(Vec3::new(1, -2, 3), Vec3::ZERO), 6);
+    assert_eq!(euclidean(Vec3::new(3, 4, 12), Vec3::ZERO), 13.0);
+}
+
+#[test]
+fn cuboids() {
+    let a = Cuboid::from_ranges(0..=9, 0..=9, 0..=9);
+    let b = Cuboid::from_ranges(5..=14, -5..=4, 3..=6);
+
+    let common = a.intersection(&b).unwrap();
+    assert_eq!(common, Cuboid::from_ranges(5..=9, 0..=4, 3..=6));
+
+    let rest: Vec<_> = a.difference(&b).collect();
+    assert_eq!(
+        rest.iter().map(Cuboid::volume).sum::<u128>() + common.volume(),
+        a.volume()
+    );
+    for (i, piece) in rest.iter().enumerate() {
+        assert_eq!(piece.intersection(&b), None);
+        assert!(rest[..i].iter().all(|p| p.intersection(piece).is_none()));
+    }
+
+    let far = Cuboid::new(Vec3::splat(20), Vec3::splat(30));
+    assert_eq!(a.intersection(&far), None);
+    assert_eq!(a.difference(&far).collect::<Vec<_>>(), [a]);
+    assert_eq!(a.difference(&a).count(), 0);
+}
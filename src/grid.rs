@@ -0,0 +1,348 @@
+//! Rectangular 2D maps, stored row after row in one contiguous `Vec`.
+//!
+//! Positions are `(row, column)` pairs, with `(0, 0)` in the top left corner.
+
+use std::{
+    borrow::Borrow,
+    fmt::{self, Display},
+    ops::{Index, IndexMut, RangeInclusive},
+};
+
+use crate::error::{ParseError, Source};
+
+/// `(row, column)`
+pub type Pos = (usize, usize);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width`x`height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A `width`x`height` grid with every cell set to `cell(pos)`.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Pos) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|row| (0..width).map(move |col| (row, col)))
+                .map(&mut cell)
+                .collect(),
+        }
+    }
+
+    /// The grid made of `rows`, or `None` if they don't all have the same length.
+    pub fn from_rows(rows: impl IntoIterator<Item = Vec<T>>) -> Option<Self> {
+        let mut rows = rows.into_iter().peekable();
+        let width = rows.peek().map_or(0, Vec::len);
+
+        let mut grid = Grid {
+            width,
+            height: 0,
+            cells: Vec::new(),
+        };
+        for row in rows {
+            if row.len() != width {
+                return None;
+            }
+            grid.cells.extend(row);
+            grid.height += 1;
+        }
+        Some(grid)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.0 * self.width + pos.1)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(move |row| self.row(row))
+    }
+
+    /// Cells of one column, from top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// All cells, row after row.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// All positions, row after row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their position, row after row.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Positions above, left, right and below `pos` (in that order) that are inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &[(-1, 0), (0, -1), (0, 1), (1, 0)])
+    }
+
+    /// Positions around `pos`, diagonals included, that are inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(
+            pos,
+            &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn offsets(
+        &self,
+        (row, col): Pos,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> {
+        // no borrow of the grid, so cells can be updated while walking over neighbours
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(d_row, d_col)| {
+            let row = row.checked_add_signed(d_row)?;
+            let col = col.checked_add_signed(d_col)?;
+            (row < height && col < width).then_some((row, col))
+        })
+    }
+
+    /// Same grid with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<bool> {
+    /// The smallest grid holding all `points` (`(row, column)` pairs, which may be negative
+    /// or far from 0), with exactly these cells set. Also returns the point the top left
+    /// cell stands for.
+    pub fn from_points<T, P>(points: impl IntoIterator<Item = P>) -> (Self, (isize, isize))
+    where
+        P: Borrow<(T, T)>,
+        T: Copy + TryInto<isize>,
+    {
+        let to_isize = |v: T| {
+            v.try_into()
+                .unwrap_or_else(|_| panic!("coordinate too large for a grid"))
+        };
+        let points: Vec<(isize, isize)> = points
+            .into_iter()
+            .map(|p| {
+                let &(row, col) = p.borrow();
+                (to_isize(row), to_isize(col))
+            })
+            .collect();
+
+        let (rows, cols) = match bounding_box(points.iter().copied()) {
+            Some(bounds) => bounds,
+            None => return (Grid::new(0, 0, false), (0, 0)),
+        };
+        let origin = (*rows.start(), *cols.start());
+        let mut grid = Grid::new(
+            (cols.end() - cols.start() + 1) as usize,
+            (rows.end() - rows.start() + 1) as usize,
+            false,
+        );
+        for (row, col) in points {
+            grid[((row - origin.0) as usize, (col - origin.1) as usize)] = true;
+        }
+
+        (grid, origin)
+    }
+}
+
+/// Smallest ranges of rows and columns holding every `(row, column)` point, or `None` if
+/// there are no points.
+pub fn bounding_box(
+    points: impl IntoIterator<Item = (isize, isize)>,
+) -> Option<(RangeInclusive<isize>, RangeInclusive<isize>)> {
+    points
+        .into_iter()
+        .fold(None, |bounds, (row, col)| match bounds {
+            None => Some((row..=row, col..=col)),
+            Some((rows, cols)) => Some((
+                *rows.start().min(&row)..=*rows.end().max(&row),
+                *cols.start().min(&col)..=*cols.end().max(&col),
+            )),
+        })
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single digits, one row per line (blank lines are skipped). `cell`
+    /// names what a digit stands for, for error messages.
+    pub fn parse_digits(
+        src: &Source,
+        input: &str,
+        digits: RangeInclusive<u8>,
+        cell: &str,
+    ) -> Result<Self, ParseError> {
+        let lines = input.lines().map(str::trim).filter(|l| !l.is_empty());
+
+        let mut grid = Grid {
+            width: 0,
+            height: 0,
+            cells: Vec::with_capacity(input.len()),
+        };
+        for line in lines {
+            for (idx, c) in line.char_indices() {
+                match c.to_digit(10) {
+                    Some(d) if digits.contains(&(d as u8)) => grid.cells.push(d as u8),
+                    _ => {
+                        return Err(src.error(
+                            &line[idx..],
+                            format!(
+                                "a {} (digit between {} and {})",
+                                cell,
+                                digits.start(),
+                                digits.end()
+                            ),
+                        ))
+                    }
+                }
+            }
+
+            if grid.height == 0 {
+                grid.width = grid.cells.len();
+            } else if grid.cells.len() != (grid.height + 1) * grid.width {
+                return Err(src.error(line, format!("{} {}s per row", grid.width, cell)));
+            }
+            grid.height += 1;
+        }
+
+        if grid.cells.is_empty() {
+            return Err(src.error_at_end(format!("a grid of {}s", cell)));
+        }
+        grid.cells.shrink_to_fit();
+        Ok(grid)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} out of bounds", pos),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos) {
+            Some(idx) => &mut self.cells[idx],
+            None => panic!("{:?} out of bounds", pos),
+        }
+    }
+}
+
+/// One row per line, cells side by side. A width (`{:3}`) pads every cell to it.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                match f.width() {
+                    Some(width) => write!(f, "{:>width$}", cell, width = width)?,
+                    None => write!(f, "{}", cell)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn grid() {
+    let input = "123\n456\n";
+    let grid = Grid::parse_digits(&Source::new(0, input), input, 0..=9, "digit").unwrap();
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(1, 0)], 4);
+    assert_eq!(grid.get((2, 0)), None);
+    assert_eq!(grid.row(1), [4, 5, 6]);
+    assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+    assert_eq!(
+        grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+        [(0, 1), (1, 0)]
+    );
+    assert_eq!(grid.neighbours8((1, 1)).count(), 5);
+    assert_eq!(grid.to_string(), input);
+    assert_eq!(format!("{:2}", grid), " 1 2 3\n 4 5 6\n");
+
+    let (points, origin) = Grid::from_points([(-1_isize, 4_isize), (1, 3)]);
+    assert_eq!(origin, (-1, 3));
+    assert_eq!(
+        points
+            .to_string()
+            .replace("true", "#")
+            .replace("false", "."),
+        ".#\n..\n#.\n"
+    );
+
+    let ragged = "123\n45\n";
+    let e = Grid::parse_digits(&Source::new(0, ragged), ragged, 1..=9, "digit").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+}
@@ -0,0 +1,92 @@
+//! Cleaning up puzzle inputs before parsing, so that files saved by any editor on any OS
+//! read the same.
+
+use std::{
+    borrow::Cow,
+    io::{self, BufRead},
+};
+
+/// Puts `input` in the shape every day's parser expects:
+/// - `\n` line endings (`\r\n` and lone `\r` are converted),
+/// - no whitespace at the end of lines,
+/// - no blank lines at the end,
+/// - a single `\n` after the last line.
+///
+/// Every other line is kept where it is, blank or not, so that the line numbers of errors
+/// found while parsing the result are those of `input`: parsers skip blank lines at the
+/// start, and take any number of them wherever the puzzle separates sections with one.
+/// Inputs that are already clean are returned as is, without copying.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if is_normalized(input) {
+        return Cow::Borrowed(input);
+    }
+
+    let unified = input.replace("\r\n", "\n").replace('\r', "\n");
+    let mut out = String::with_capacity(unified.len() + 1);
+    for line in unified.split('\n').map(str::trim_end) {
+        out.push_str(line);
+        out.push('\n');
+    }
+    let len = out.trim_end_matches('\n').len();
+    out.truncate(len);
+    if !out.is_empty() {
+        out.push('\n');
+    }
+
+    Cow::Owned(out)
+}
+
+/// The lines of `reader`, read one at a time and cleaned up like [`normalize`] does for a
+/// whole input: `\r\n` and lone `\r` end lines too, and whitespace at the end of lines is
+/// dropped. Blank lines are kept, so line numbers are those of the input.
+pub fn stream_lines(reader: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+    // `lines` already splits on `\n` and `\r\n`
+    reader.lines().flat_map(|line| match line {
+        Ok(line) => line
+            .split('\r')
+            .map(|line| Ok(line.trim_end().to_string()))
+            .collect(),
+        Err(e) => vec![Err(e)],
+    })
+}
+
+/// `input` from its first line that isn't blank on, for normalized inputs. As a subslice of
+/// `input`, errors found in it still point to the right line.
+pub fn skip_blank_lines(input: &str) -> &str {
+    input.trim_start_matches('\n')
+}
+
+fn is_normalized(input: &str) -> bool {
+    input.ends_with('\n')
+        && !input.ends_with("\n\n")
+        && !input.contains('\r')
+        && input.split_terminator('\n').all(|l| l.trim_end() == l)
+}
+
+#[test]
+fn normalize_line_endings() {
+    assert_eq!(normalize("1\n2\n"), Cow::Borrowed("1\n2\n"));
+    assert!(matches!(normalize("1\n2\n"), Cow::Borrowed(_)));
+
+    assert_eq!(normalize("1\r\n2\r\n"), "1\n2\n");
+    assert_eq!(normalize("1\r2"), "1\n2\n");
+    assert_eq!(normalize("1\n2"), "1\n2\n");
+    assert_eq!(normalize("\n\n1 \t\n\n\n\n2\n\n"), "\n\n1\n\n\n\n2\n");
+    assert_eq!(normalize("1\n \n2\n").lines().count(), 3);
+    assert_eq!(normalize("  \n"), "");
+    assert_eq!(normalize(" 8  2\n"), " 8  2\n");
+}
+
+#[test]
+fn stream_line_endings() {
+    let lines = |input: &str| -> Vec<String> {
+        stream_lines(input.as_bytes())
+            .collect::<io::Result<_>>()
+            .unwrap()
+    };
+    assert_eq!(lines("1\r\n2 \r3\n\t\n4"), ["1", "2", "3", "", "4"]);
+    assert_eq!(
+        lines("\n1 \r\n \n\n2\n").join("\n") + "\n",
+        normalize("\n1 \r\n \n\n2\n")
+    );
+}
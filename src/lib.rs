@@ -0,0 +1,66 @@
+//! Advent of Code 2021 solutions.
+//!
+//! Every day lives in its own module and exposes a `parse` function that turns the raw
+//! puzzle input into that day's data structures (or a [`error::ParseError`] saying where
+//! the input is wrong), plus `part1`/`part2` functions that compute the answers from it.
+//! The programs under `src/bin/` are thin wrappers around these, printing answers through
+//! [`output::Output`].
+
+use std::{
+    error::Error,
+    io::{self, Read},
+    process,
+};
+
+pub mod animate;
+pub mod answers;
+pub mod batch;
+pub mod bench;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod generate;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod netpbm;
+pub mod output;
+pub mod search;
+pub mod solver;
+pub mod trace;
+
+/// Reads the whole standard input into a string.
+pub fn read_stdin() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().lock().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Runs the body of a program under `src/bin/`, printing its error (with `Display`, where
+/// returning it from `main` would print its `Debug`) and exiting with status 1 if it fails.
+pub fn run_main(run: impl FnOnce() -> Result<(), Box<dyn Error>>) {
+    if let Err(e) = run() {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,192 @@
+//! Saving grids and sets of points as Netpbm images: black and white (PBM), grey (PGM) or
+//! colour (PPM).
+//!
+//! Images are written in the plain (text) variants of the formats, which any image viewer
+//! opens and which text tools can diff.
+
+use std::fmt::{self, Display};
+
+use crate::{day13, day15, day20, day5, day9, error::ParseError, grid::Grid};
+
+/// `[red, green, blue]`
+pub type Rgb = [u8; 3];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Image {
+    /// Set pixels are black.
+    Bitmap(Grid<bool>),
+    /// 0 is black, 255 is white.
+    Greymap(Grid<u8>),
+    Pixmap(Grid<Rgb>),
+}
+
+impl Image {
+    /// Black pixels at `points` (`(row, column)` pairs), in their bounding box.
+    pub fn from_points<T, P>(points: impl IntoIterator<Item = P>) -> Self
+    where
+        P: std::borrow::Borrow<(T, T)>,
+        T: Copy + TryInto<isize>,
+    {
+        Image::Bitmap(Grid::from_points(points).0)
+    }
+
+    /// Grey levels given by `shade` for every cell of `grid`.
+    pub fn greymap<T>(grid: &Grid<T>, shade: impl FnMut(&T) -> u8) -> Self {
+        Image::Greymap(grid.map(shade))
+    }
+
+    /// Colours given by `palette` for every cell of `grid`.
+    pub fn pixmap<T>(grid: &Grid<T>, palette: impl FnMut(&T) -> Rgb) -> Self {
+        Image::Pixmap(grid.map(palette))
+    }
+
+    /// The file extension for the image's format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Image::Bitmap(_) => "pbm",
+            Image::Greymap(_) => "pgm",
+            Image::Pixmap(_) => "ppm",
+        }
+    }
+}
+
+/// The whole image file.
+impl Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Image::Bitmap(grid) => {
+                writeln!(f, "P1\n{} {}", grid.width(), grid.height())?;
+                write_rows(f, grid, |&set| if set { "1" } else { "0" }.to_string())
+            }
+            Image::Greymap(grid) => {
+                writeln!(f, "P2\n{} {}\n255", grid.width(), grid.height())?;
+                write_rows(f, grid, u8::to_string)
+            }
+            Image::Pixmap(grid) => {
+                writeln!(f, "P3\n{} {}\n255", grid.width(), grid.height())?;
+                write_rows(f, grid, |[r, g, b]| format!("{} {} {}", r, g, b))
+            }
+        }
+    }
+}
+
+/// One row per line, except that lines are wrapped to 70 characters as the formats require.
+fn write_rows<T>(
+    f: &mut fmt::Formatter<'_>,
+    grid: &Grid<T>,
+    mut value: impl FnMut(&T) -> String,
+) -> fmt::Result {
+    const MAX_LINE: usize = 70;
+
+    for row in grid.rows() {
+        let mut line_len = 0;
+        for cell in row {
+            let value = value(cell);
+            if line_len > 0 && line_len + 1 + value.len() > MAX_LINE {
+                writeln!(f)?;
+                line_len = 0;
+            }
+            if line_len > 0 {
+                f.write_str(" ")?;
+                line_len += 1;
+            }
+            f.write_str(&value)?;
+            line_len += value.len();
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+/// Days that can be exported.
+pub const DAYS: [u8; 5] = [5, 9, 13, 15, 20];
+
+/// An image of a day's result, or `None` for days without one:
+/// - day 5: how many vents lines (diagonals included) cover every point, darker for more,
+/// - day 9: every basin in its own colour, high points in black,
+/// - day 13: the dots after all folds,
+/// - day 15: risk levels, darker for higher, with the safest path in red,
+/// - day 20: the image after 50 enhancements.
+pub fn export(day: u8, input: &str) -> Result<Option<Image>, ParseError> {
+    Ok(Some(match day {
+        5 => {
+            let lines = day5::parse(input)?;
+            let counts = day5::line_counts(lines.iter());
+            let max = counts.values().copied().max().unwrap_or(1);
+
+            let (covered, (top, left)) = Grid::from_points(counts.keys().map(|&(x, y)| (y, x)));
+            let counts = Grid::from_fn(covered.width(), covered.height(), |(row, col)| {
+                let point = (left as i32 + col as i32, top as i32 + row as i32);
+                counts.get(&point).copied().unwrap_or(0)
+            });
+            Image::greymap(&counts, |&count| (255 - count * 255 / max) as u8)
+        }
+        9 => {
+            const PALETTE: [Rgb; 8] = [
+                [230, 25, 75],
+                [60, 180, 75],
+                [255, 225, 25],
+                [0, 130, 200],
+                [245, 130, 48],
+                [145, 30, 180],
+                [70, 240, 240],
+                [240, 50, 230],
+            ];
+
+            let map = day9::parse(input)?;
+            let (mut basins, _) = day9::basin_seeds(&map);
+            while day9::grow_basins(&map, &mut basins) {}
+            Image::pixmap(&basins, |&id| match id {
+                u32::MAX => [0, 0, 0],
+                id => PALETTE[id as usize % PALETTE.len()],
+            })
+        }
+        13 => {
+            let (mut dots, folds) = day13::parse(input)?;
+            for fold in folds {
+                day13::fold_along(&mut dots, fold);
+            }
+            Image::from_points(dots.iter().map(|&(x, y)| (y, x)))
+        }
+        15 => {
+            let map = day15::parse(input)?;
+            let (_, path) = day15::lowest_risk_path(&map);
+
+            let mut pixels = map.map(|&risk| [255 - 25 * risk; 3]);
+            for pos in path {
+                pixels[pos] = [255, 0, 0];
+            }
+            Image::Pixmap(pixels)
+        }
+        20 => {
+            let (mut image, algo_map) = day20::parse(input)?;
+            for _ in 0..50 {
+                image = day20::enhance(image, &algo_map);
+            }
+            Image::from_points(&image.0)
+        }
+        _ => return Ok(None),
+    }))
+}
+
+#[test]
+fn plain_formats() {
+    let grid = Grid::from_rows([vec![1_u8, 2], vec![3, 4]]).unwrap();
+
+    assert_eq!(
+        Image::from_points([(0_usize, 0_usize), (1, 1)]).to_string(),
+        "P1\n2 2\n1 0\n0 1\n"
+    );
+    assert_eq!(
+        Image::greymap(&grid, |&v| v * 10).to_string(),
+        "P2\n2 2\n255\n10 20\n30 40\n"
+    );
+    assert_eq!(
+        Image::pixmap(&grid, |&v| [v, 0, 255]).to_string(),
+        "P3\n2 2\n255\n1 0 255 2 0 255\n3 0 255 4 0 255\n"
+    );
+
+    let wide = Image::Greymap(Grid::new(30, 1, 255)).to_string();
+    assert!(wide.lines().all(|l| l.len() <= 70));
+    assert_eq!(wide.lines().count(), 3 + 2);
+}
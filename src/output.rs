@@ -0,0 +1,120 @@
+//! Printing answers, either for people (`plain`) or for other programs (`json`).
+//!
+//! Answers always go to standard output, one record per answer. Anything else a program
+//! wants to show (intermediate states, drawings) goes to standard error, and only with
+//! `--verbose`.
+
+use std::fmt::Write;
+
+use clap::{ArgEnum, Parser};
+
+use crate::solver::{Answer, Part};
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// `day 11 part 1: 1656`
+    Plain,
+    /// `{"day":11,"part":1,"answer":1656}`
+    Json,
+}
+
+/// Output options shared by every program.
+#[derive(Parser, Clone, Debug)]
+#[clap(about = "Solves the puzzle input read from standard input")]
+pub struct Output {
+    /// How to print answers
+    #[clap(long, arg_enum, default_value = "plain")]
+    pub format: Format,
+
+    /// Also print intermediate results, on standard error
+    #[clap(long)]
+    pub verbose: bool,
+}
+
+impl Output {
+    /// Output options from the command line of a program that takes no other arguments.
+    pub fn from_args() -> Self {
+        Output::parse()
+    }
+
+    /// Prints the answer to one part of a day's puzzle.
+    pub fn answer(&self, day: u8, part: Part, answer: impl Into<Answer>) {
+        println!("{}", self.record(day, part, &answer.into()));
+    }
+
+    /// Formats an answer the way [`Output::answer`] prints it (without the final newline).
+    pub fn record(&self, day: u8, part: Part, answer: &Answer) -> String {
+        match self.format {
+            Format::Plain => {
+                let answer = answer.to_string();
+                if answer.contains('\n') {
+                    format!("day {} part {}:\n{}", day, part, answer.trim_end())
+                } else {
+                    format!("day {} part {}: {}", day, part, answer)
+                }
+            }
+            Format::Json => {
+                let answer = match answer {
+                    Answer::Number(n) => n.to_string(),
+                    Answer::Text(s) => json_string(s),
+                };
+                format!(r#"{{"day":{},"part":{},"answer":{}}}"#, day, part, answer)
+            }
+        }
+    }
+
+    /// Prints whatever `message` returns on standard error, but only in verbose mode (so
+    /// the message isn't even built otherwise).
+    pub fn debug<S: AsRef<str>>(&self, message: impl FnOnce() -> S) {
+        if self.verbose {
+            eprintln!("{}", message().as_ref());
+        }
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[test]
+fn records() {
+    let plain = Output {
+        format: Format::Plain,
+        verbose: false,
+    };
+    let json = Output {
+        format: Format::Json,
+        ..plain.clone()
+    };
+
+    let number = Answer::Number(1656);
+    assert_eq!(plain.record(11, Part::One, &number), "day 11 part 1: 1656");
+    assert_eq!(
+        json.record(11, Part::One, &number),
+        r#"{"day":11,"part":1,"answer":1656}"#
+    );
+
+    let text = Answer::Text("# \"#\"\n#\\#\n".to_string());
+    assert_eq!(
+        plain.record(13, Part::Two, &text),
+        "day 13 part 2:\n# \"#\"\n#\\#"
+    );
+    assert_eq!(
+        json.record(13, Part::Two, &text),
+        r##"{"day":13,"part":2,"answer":"# \"#\"\n#\\#\n"}"##
+    );
+}
@@ -0,0 +1,283 @@
+//! Graph searches (breadth-first, Dijkstra, A*) over graphs given by the neighbours of
+//! each node.
+//!
+//! Searches record the best known distance and predecessor of every node they reach, so
+//! that the path to any of them can be rebuilt afterwards.
+
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Cost of going along an edge, or of a whole path.
+pub type Cost = u64;
+
+/// The edges leaving every node of a graph, with their costs.
+///
+/// Any `Fn(&N) -> impl IntoIterator<Item = (N, Cost)>` closure is such a graph.
+pub trait Neighbours<N> {
+    type Edges: IntoIterator<Item = (N, Cost)>;
+
+    fn neighbours(&self, node: &N) -> Self::Edges;
+}
+
+impl<N, F, E> Neighbours<N> for F
+where
+    F: Fn(&N) -> E,
+    E: IntoIterator<Item = (N, Cost)>,
+{
+    type Edges = E;
+
+    fn neighbours(&self, node: &N) -> E {
+        self(node)
+    }
+}
+
+/// What a search found out about the nodes it reached.
+#[derive(Clone, Debug)]
+pub struct Search<N> {
+    start: N,
+    dist: HashMap<N, Cost>,
+    prev: HashMap<N, N>,
+    /// The goal the search stopped at, if it found one.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn new(start: N) -> Self {
+        Search {
+            dist: HashMap::from([(start.clone(), 0)]),
+            prev: HashMap::new(),
+            start,
+            goal: None,
+        }
+    }
+
+    /// Lowest cost from the start to `node`, if the search reached it.
+    ///
+    /// Only final for nodes the search was done with, i.e. not for nodes that were still
+    /// queued when it found its goal.
+    pub fn distance(&self, node: &N) -> Option<Cost> {
+        self.dist.get(node).copied()
+    }
+
+    /// Lowest costs of every node reached.
+    pub fn distances(&self) -> &HashMap<N, Cost> {
+        &self.dist
+    }
+
+    /// The node before each node on its best path from the start.
+    pub fn predecessors(&self) -> &HashMap<N, N> {
+        &self.prev
+    }
+
+    /// Nodes from the start to `node` (both included), if the search reached it.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        debug_assert!(path[0] == self.start);
+        Some(path)
+    }
+
+    /// Cost and nodes of the path to the goal, if one was found.
+    pub fn goal_path(&self) -> Option<(Cost, Vec<N>)> {
+        let goal = self.goal.as_ref()?;
+        Some((self.dist[goal], self.path_to(goal)?))
+    }
+}
+
+/// Visits nodes by number of edges from `start` (ignoring costs: every edge counts as 1),
+/// until one satisfies `is_goal`. `|_| false` visits every node reachable.
+pub fn bfs<N: Clone + Eq + Hash>(
+    graph: &impl Neighbours<N>,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N> {
+    let mut search = Search::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        let d = search.dist[&node] + 1;
+        for (next, _) in graph.neighbours(&node) {
+            if let Entry::Vacant(entry) = search.dist.entry(next.clone()) {
+                entry.insert(d);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    search
+}
+
+/// Visits nodes by lowest total cost from `start`, until one satisfies `is_goal`.
+pub fn dijkstra<N: Clone + Eq + Hash>(
+    graph: &impl Neighbours<N>,
+    start: N,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N> {
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Like [`dijkstra`], but nodes that `heuristic` estimates closer to the goal are visited
+/// first. The goal's cost is only the lowest if `heuristic` never overestimates the cost
+/// left.
+pub fn astar<N: Clone + Eq + Hash>(
+    graph: &impl Neighbours<N>,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    heuristic: impl Fn(&N) -> Cost,
+) -> Search<N> {
+    let mut search = Search::new(start.clone());
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: 0,
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if cost > search.dist[&node] {
+            // already visited through a better path
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+
+        for (next, edge) in graph.neighbours(&node) {
+            let d = cost + edge;
+            if search.dist.get(&next).is_none_or(|&known| d < known) {
+                search.dist.insert(next.clone(), d);
+                search.prev.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: d + heuristic(&next),
+                    cost: d,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    search
+}
+
+/// A node waiting in the priority queue, lowest priority first.
+struct Queued<N> {
+    priority: Cost,
+    cost: Cost,
+    node: N,
+}
+
+impl<N> PartialEq for Queued<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N> Eq for Queued<N> {}
+
+impl<N> PartialOrd for Queued<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Queued<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, BinaryHeap is a max-heap
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Number of different paths from `start` to a node satisfying `is_goal` (paths stop at
+/// the first goal they reach). The graph must have no cycles, or there would be infinitely
+/// many paths; nodes reachable through many paths are only explored once.
+pub fn count_paths<N: Clone + Eq + Hash>(
+    graph: &impl Neighbours<N>,
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize {
+    fn count<N: Clone + Eq + Hash>(
+        graph: &impl Neighbours<N>,
+        node: N,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        counts: &mut HashMap<N, usize>,
+    ) -> usize {
+        if let Some(&n) = counts.get(&node) {
+            return n;
+        }
+
+        let n = if is_goal(&node) {
+            1
+        } else {
+            graph
+                .neighbours(&node)
+                .into_iter()
+                .map(|(next, _)| count(graph, next, is_goal, counts))
+                .sum()
+        };
+        counts.insert(node, n);
+        n
+    }
+
+    count(graph, start, &mut is_goal, &mut HashMap::new())
+}
+
+#[test]
+fn searches() {
+    //  0 -1- 1 -1- 2
+    //  |           |
+    //  5           1
+    //  |           |
+    //  4 ----1---- 3
+    let graph = |&node: &u8| -> Vec<(u8, Cost)> {
+        match node {
+            0 => vec![(1, 1), (4, 5)],
+            1 => vec![(0, 1), (2, 1)],
+            2 => vec![(1, 1), (3, 1)],
+            3 => vec![(2, 1), (4, 1)],
+            _ => vec![(3, 1), (0, 5)],
+        }
+    };
+
+    let by_edges = bfs(&graph, 0, |_| false);
+    assert_eq!(by_edges.distance(&4), Some(1));
+    assert_eq!(by_edges.path_to(&3), Some(vec![0, 4, 3]));
+    assert_eq!(by_edges.goal, None);
+
+    let by_cost = dijkstra(&graph, 0, |&n| n == 4);
+    assert_eq!(by_cost.goal_path(), Some((4, vec![0, 1, 2, 3, 4])));
+    assert_eq!(by_cost.predecessors()[&3], 2);
+
+    // the heuristic isn't admissible, so the worse path is found first
+    let misled = astar(&graph, 0, |&n| n == 4, |&n| if n == 1 { 10 } else { 0 });
+    assert_eq!(misled.goal_path(), Some((5, vec![0, 4])));
+
+    assert_eq!(dijkstra(&graph, 0, |&n| n == 9).goal_path(), None);
+    assert_eq!(dijkstra(&graph, 0, |_| false).distances().len(), 5);
+
+    // 0 → 1, 2 → 3 → 4: 2 paths to 3, 2 to 4
+    let dag = |&node: &u8| match node {
+        0 => vec![(1, 1), (2, 1)],
+        1 | 2 => vec![(3, 1)],
+        3 => vec![(4, 1)],
+        _ => vec![],
+    };
+    assert_eq!(count_paths(&dag, 0, |&n| n == 4), 2);
+    assert_eq!(count_paths(&dag, 0, |&n| n >= 3), 2);
+    assert_eq!(count_paths(&dag, 0, |&n| n == 0), 1);
+}
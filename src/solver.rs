@@ -0,0 +1,270 @@
+//! Type-erased registry of every day's solvers, so they can be picked at runtime.
+
+use std::fmt::{self, Display};
+
+use crate::{error::ParseError, *};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.number(), f)
+    }
+}
+
+/// A puzzle answer. Almost all of them are numbers, a few are drawings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => Display::fmt(n, f),
+            Answer::Text(s) => Display::fmt(s, f),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+impl_answer_from_int!(u32, u64, u128, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// Why a part has no answer for an input that parsed fine (e.g. octopuses that never all
+/// flash at once).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NoAnswer(pub String);
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no answer: {}", self.0)
+    }
+}
+
+impl std::error::Error for NoAnswer {}
+
+impl From<&str> for NoAnswer {
+    fn from(reason: &str) -> Self {
+        NoAnswer(reason.to_string())
+    }
+}
+
+/// What part functions return: an answer, or a `Result` of one for parts that some inputs
+/// have no answer to.
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<Answer, NoAnswer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> Result<Answer, NoAnswer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>> IntoAnswer for Result<T, NoAnswer> {
+    fn into_answer(self) -> Result<Answer, NoAnswer> {
+        self.map(Into::into)
+    }
+}
+
+/// Parsed input for some solver, ready to compute its answers.
+pub trait Parsed {
+    /// `None` if the solver can't answer this part.
+    fn solve(&self, part: Part) -> Option<Result<Answer, NoAnswer>>;
+}
+
+type PartFn<I> = fn(&I) -> Result<Answer, NoAnswer>;
+type ErasedParseFn = Box<dyn Fn(&str) -> Result<Box<dyn Parsed>, ParseError> + Send + Sync>;
+
+struct ParsedInput<I> {
+    input: I,
+    parts: [Option<PartFn<I>>; 2],
+}
+
+impl<I> Parsed for ParsedInput<I> {
+    fn solve(&self, part: Part) -> Option<Result<Answer, NoAnswer>> {
+        self.parts[part.number() as usize - 1].map(|f| f(&self.input))
+    }
+}
+
+/// One way of solving a day's puzzle.
+///
+/// Most days have a single `default` variant, a few keep around alternative implementations
+/// (e.g. day 14's `parallel`).
+pub struct Solver {
+    pub day: u8,
+    pub variant: &'static str,
+    pub description: &'static str,
+    parts: Vec<Part>,
+    parse: ErasedParseFn,
+}
+
+impl Solver {
+    fn new<I: 'static>(
+        day: u8,
+        variant: &'static str,
+        description: &'static str,
+        parse: fn(&str) -> Result<I, ParseError>,
+        parts: [Option<PartFn<I>>; 2],
+    ) -> Self {
+        Solver {
+            day,
+            variant,
+            description,
+            parts: Part::ALL
+                .into_iter()
+                .filter(|p| parts[p.number() as usize - 1].is_some())
+                .collect(),
+            parse: Box::new(move |input| {
+                Ok(Box::new(ParsedInput {
+                    input: parse(input)?,
+                    parts,
+                }))
+            }),
+        }
+    }
+
+    /// The parts this solver knows how to answer.
+    pub fn parts(&self) -> &[Part] {
+        &self.parts
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Parses `input` and answers the requested part (`None` if this solver can't).
+    pub fn solve(
+        &self,
+        input: &str,
+        part: Part,
+    ) -> Result<Option<Result<Answer, NoAnswer>>, ParseError> {
+        Ok(self.parse(input)?.solve(part))
+    }
+}
+
+macro_rules! part {
+    ($f:path) => {
+        Some(|input| $crate::solver::IntoAnswer::into_answer($f(input)))
+    };
+}
+
+macro_rules! solver {
+    ($day:literal, $variant:literal, $description:literal, $module:ident, $part1:expr, $part2:expr) => {
+        Solver::new(
+            $day,
+            $variant,
+            $description,
+            $module::parse,
+            [$part1, $part2],
+        )
+    };
+    ($day:literal, $module:ident) => {
+        solver!(
+            $day,
+            "default",
+            "",
+            $module,
+            part!($module::part1),
+            part!($module::part2)
+        )
+    };
+}
+
+/// Every registered solver, sorted by day.
+pub fn solvers() -> Vec<Solver> {
+    vec![
+        solver!(1, day1),
+        solver!(2, day2),
+        solver!(3, day3),
+        solver!(4, day4),
+        solver!(5, day5),
+        solver!(6, day6),
+        solver!(7, day7),
+        solver!(8, day8),
+        solver!(9, day9),
+        solver!(10, day10),
+        solver!(11, day11),
+        solver!(12, day12),
+        solver!(13, day13),
+        solver!(
+            14,
+            "default",
+            "builds the polymer for part 1, tracks pair counts for part 2",
+            day14,
+            part!(day14::part1),
+            part!(day14::part2)
+        ),
+        solver!(
+            14,
+            "pair-counts",
+            "tracks pair counts for both parts",
+            day14,
+            part!(day14::part1_pair_counts),
+            part!(day14::part2)
+        ),
+        solver!(
+            14,
+            "parallel",
+            "expands every pair recursively, one thread per pair",
+            day14,
+            None,
+            part!(day14::part2_parallel)
+        ),
+        solver!(15, day15),
+        solver!(16, day16),
+        solver!(17, day17),
+        solver!(18, day18),
+        solver!(19, day19),
+        solver!(20, day20),
+        solver!(21, day21),
+        solver!(22, day22),
+    ]
+}
+
+/// Looks up the solver for `day` with the given variant.
+pub fn find(day: u8, variant: &str) -> Option<Solver> {
+    solvers()
+        .into_iter()
+        .find(|s| s.day == day && s.variant == variant)
+}
@@ -0,0 +1,107 @@
+//! Debug events from inside the solvers (caves being visited, octopuses flashing...),
+//! printed on standard error.
+//!
+//! Events only exist in builds with the `trace` feature: without it, [`trace!`] compiles to
+//! nothing and its arguments are never evaluated. With it, a day's events are printed once
+//! the day is enabled, with [`enable`] or with the `AOC_TRACE` environment variable
+//! (`AOC_TRACE=11,12`, or `AOC_TRACE=all`).
+//!
+//! Each event is one line: the day, the event's name, then its fields in `Debug` form:
+//!
+//! ```text
+//! [day12] expand state cave="start" can_visit_twice=false
+//! ```
+//!
+//! [`trace!`]: crate::trace!
+
+use std::{
+    fmt::Debug,
+    io::Write,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        OnceLock,
+    },
+};
+
+/// Whether this build has the `trace` feature, i.e. whether there are any events to print.
+pub const COMPILED: bool = cfg!(feature = "trace");
+
+/// Prints an event if its day is enabled: `trace!(day, "name", field = value, ...)`.
+#[cfg(feature = "trace")]
+#[macro_export]
+macro_rules! trace {
+    ($day:expr, $event:expr $(, $field:ident = $value:expr)* $(,)?) => {{
+        if $crate::trace::enabled($day) {
+            $crate::trace::event(
+                $day,
+                $event,
+                &[$((stringify!($field), &$value as &dyn ::std::fmt::Debug)),*],
+            );
+        }
+    }};
+}
+
+/// Prints an event if its day is enabled: `trace!(day, "name", field = value, ...)`.
+#[cfg(not(feature = "trace"))]
+#[macro_export]
+macro_rules! trace {
+    // never runs, but keeps events type-checked (and their variables used) in every build
+    ($day:expr, $event:expr $(, $field:ident = $value:expr)* $(,)?) => {{
+        if false {
+            let _ = ($day, $event);
+            $(let _ = &$value;)*
+        }
+    }};
+}
+
+/// One bit per day, bit 0 for day 1.
+fn enabled_days() -> &'static AtomicU32 {
+    static DAYS: OnceLock<AtomicU32> = OnceLock::new();
+    DAYS.get_or_init(|| {
+        let days = std::env::var("AOC_TRACE").unwrap_or_default();
+        AtomicU32::new(parse_days(&days))
+    })
+}
+
+fn parse_days(days: &str) -> u32 {
+    days.split(',')
+        .map(str::trim)
+        .fold(0, |mask, day| match day {
+            "all" => u32::MAX,
+            _ => match day.parse::<u8>() {
+                Ok(day @ 1..=25) => mask | 1 << (day - 1),
+                _ => mask,
+            },
+        })
+}
+
+pub fn enabled(day: u8) -> bool {
+    (1..=25).contains(&day) && enabled_days().load(Ordering::Relaxed) & 1 << (day - 1) != 0
+}
+
+/// Starts printing the events of `day` (on top of the days in `AOC_TRACE`).
+pub fn enable(day: u8) {
+    if (1..=25).contains(&day) {
+        enabled_days().fetch_or(1 << (day - 1), Ordering::Relaxed);
+    }
+}
+
+/// Prints an event, whether its day is enabled or not. Use [`trace!`](crate::trace!)
+/// instead.
+pub fn event(day: u8, name: &str, fields: &[(&str, &dyn Debug)]) {
+    let mut line = format!("[day{}] {}", day, name);
+    for (field, value) in fields {
+        line += &format!(" {}={:?}", field, value);
+    }
+    line.push('\n');
+
+    // a single write, so events from different threads don't interleave
+    let _ = std::io::stderr().lock().write_all(line.as_bytes());
+}
+
+#[test]
+fn days() {
+    assert_eq!(parse_days(""), 0);
+    assert_eq!(parse_days("1, 12,nope,30"), 1 | 1 << 11);
+    assert_eq!(parse_days("3,all"), u32::MAX);
+}
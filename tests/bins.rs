@@ -0,0 +1,99 @@
+//! The standalone binaries, run on small inputs.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Runs `bin` with `args` and `input` on standard input, returning whether it succeeded
+/// and what it printed, on standard output then standard error.
+fn run(bin: &str, args: &[&str], input: &str) -> (bool, String, String) {
+    let mut child = Command::new(bin)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn day1_empty_report() {
+    let day1 = env!("CARGO_BIN_EXE_day1");
+    assert_eq!(
+        run(day1, &[], ""),
+        (
+            true,
+            "day 1 part 1: 0\nday 1 part 2: 0\n".to_string(),
+            String::new()
+        )
+    );
+    assert_eq!(
+        run(day1, &["--window", "2"], "\n"),
+        (true, "day 1 window 2: 0\n".to_string(), String::new())
+    );
+}
+
+#[test]
+fn day1_messy_report() {
+    // the same report as `aoc run -d 1` reads it, whatever the line endings and spacing
+    let expected = (
+        true,
+        "day 1 part 1: 3\nday 1 part 2: 1\n".to_string(),
+        String::new(),
+    );
+    let day1 = env!("CARGO_BIN_EXE_day1");
+    assert_eq!(run(day1, &[], "1\r2\r3\r1\r4\r"), expected);
+    assert_eq!(run(day1, &[], "  1\r\n 2 \n\n3\n\t1\n4 \n"), expected);
+    assert_eq!(
+        run(day1, &[], "1\n2\rx\n"),
+        (
+            false,
+            String::new(),
+            "error: day 1 input, line 3, column 1: expected a depth measurement (integer)\n"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn day1_profile_json() {
+    let (ok, out, _) = run(
+        env!("CARGO_BIN_EXE_day1"),
+        &["--profile", "--format", "json"],
+        "1\n2\n",
+    );
+    assert!(ok);
+    assert_eq!(
+        out.lines().collect::<Vec<_>>(),
+        [
+            r#"{"day":1,"run":{"trend":"ascending","start":0,"length":2,"change":1}}"#,
+            r#"{"day":1,"longest_descent":null}"#,
+            r#"{"day":1,"largest_jump":{"at":1,"change":1}}"#,
+        ]
+    );
+}
+
+#[test]
+fn bad_input() {
+    assert_eq!(
+        run(env!("CARGO_BIN_EXE_day3"), &[], "x\n"),
+        (
+            false,
+            String::new(),
+            "error: day 3 input, line 1, column 1: expected a binary digit\n".to_string()
+        )
+    );
+}
@@ -0,0 +1,110 @@
+//! Optimized solvers against slow but obviously correct ones, on generated inputs.
+
+use std::collections::HashSet;
+
+use aoc2021::{
+    day1, day14, day22,
+    day22::{Cuboid, Instr},
+    day6,
+    generate::generate,
+};
+
+const SEEDS: std::ops::Range<u64> = 0..20;
+
+#[test]
+fn day1_sweep_matches_window_sums() {
+    for seed in SEEDS {
+        let depths = day1::parse(&generate(1, 60, seed).unwrap()).unwrap();
+
+        for window in 1..=12 {
+            let sums: Vec<i64> = depths.windows(window).map(|w| w.iter().sum()).collect();
+            let increases = sums.windows(2).filter(|pair| pair[1] > pair[0]).count();
+
+            assert_eq!(
+                day1::count_increases(depths.iter().copied(), window),
+                increases,
+                "seed {}, window {}",
+                seed,
+                window
+            );
+        }
+    }
+}
+
+#[test]
+fn day6_buckets_match_every_fish() {
+    for seed in SEEDS {
+        let counts = day6::parse(&generate(6, 20, seed).unwrap()).unwrap();
+
+        let mut school: Vec<usize> = (0..9)
+            .flat_map(|timer| std::iter::repeat_n(timer, counts[timer]))
+            .collect();
+        for days in 0..=80 {
+            assert_eq!(day6::simulate(counts, days), school.len(), "seed {}", seed);
+
+            // one day for every fish
+            let mut newborns = 0;
+            for timer in &mut school {
+                if *timer == 0 {
+                    *timer = 6;
+                    newborns += 1;
+                } else {
+                    *timer -= 1;
+                }
+            }
+            school.extend(std::iter::repeat_n(8, newborns));
+        }
+    }
+}
+
+#[test]
+fn day14_variants_agree() {
+    for seed in SEEDS {
+        let (template, rules) = day14::parse(&generate(14, 10, seed).unwrap()).unwrap();
+
+        let mut polymer = template.clone();
+        for steps in 0..=10 {
+            let expected = day14::el_frequencies(&polymer);
+            let pair_counts = day14::polymer_freqs_after_steps(&template, &rules, steps);
+            let parallel = day14::polymer_freqs_after_steps_parallel(&template, &rules, steps);
+
+            assert_eq!(pair_counts, expected, "seed {}", seed);
+            assert_eq!(parallel, expected, "seed {}", seed);
+
+            polymer = day14::step_polymer(polymer, &rules);
+        }
+
+        let input = (template, rules);
+        assert_eq!(day14::part1(&input), day14::part1_pair_counts(&input));
+    }
+}
+
+#[test]
+fn day22_reactor_core_matches_voxels() {
+    let region = Cuboid::from_ranges(-20..=20, -15..=15, -10..=10);
+
+    for seed in SEEDS {
+        let instrs = day22::parse(&generate(22, 30, seed).unwrap()).unwrap();
+
+        let mut voxels = HashSet::new();
+        for instr in &instrs {
+            let (Instr::On(c) | Instr::Off(c)) = instr;
+            let on = matches!(instr, Instr::On(_));
+
+            for x in c.min.x.max(region.min.x)..=c.max.x.min(region.max.x) {
+                for y in c.min.y.max(region.min.y)..=c.max.y.min(region.max.y) {
+                    for z in c.min.z.max(region.min.z)..=c.max.z.min(region.max.z) {
+                        if on {
+                            voxels.insert((x, y, z));
+                        } else {
+                            voxels.remove(&(x, y, z));
+                        }
+                    }
+                }
+            }
+        }
+
+        let core = day22::reboot(&instrs, &region);
+        assert_eq!(core.on_count(), voxels.len() as u128, "seed {}", seed);
+    }
+}
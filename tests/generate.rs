@@ -0,0 +1,43 @@
+//! Generated inputs must be accepted and solved by every day's default solver.
+
+use aoc2021::{generate::generate, solver};
+
+/// Sizes small enough to solve quickly in debug builds.
+const SIZES: [usize; 22] = [
+    50, 20, 30, 5, 30, 20, 30, 10, 15, 20, 10, 6, 40, 10, 15, 15, 40, 6, 5, 10, 0, 12,
+];
+
+#[test]
+fn generated_inputs_solve() {
+    for day in 1..=22 {
+        let solver = solver::find(day, "default").unwrap();
+        for seed in 0..3 {
+            let size = SIZES[day as usize - 1];
+            let input = generate(day, size, seed).unwrap();
+            let parsed = solver
+                .parse(&input)
+                .unwrap_or_else(|e| panic!("{} (seed {})\n{}", e, seed, input));
+
+            for &part in solver.parts() {
+                let answer = parsed.solve(part).unwrap();
+                assert!(
+                    answer.is_ok(),
+                    "day {} part {} (seed {}): {:?}",
+                    day,
+                    part,
+                    seed,
+                    answer
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn generation_is_deterministic() {
+    for day in 1..=22 {
+        assert_eq!(generate(day, 10, 42), generate(day, 10, 42));
+    }
+    assert_ne!(generate(1, 10, 1), generate(1, 10, 2));
+    assert_eq!(generate(23, 10, 0), None);
+}
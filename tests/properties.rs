@@ -0,0 +1,111 @@
+//! Round trips and invariants checked on random values, for the parts where the puzzle
+//! examples leave edge cases out.
+
+use aoc2021::{
+    day16::{self, OperatorType, Packet},
+    day18::{self, SNum},
+    day22::Cuboid,
+    geometry::{euclidean, manhattan, Mat3, Vec3},
+};
+use proptest::prelude::*;
+
+fn snum() -> impl Strategy<Value = SNum> {
+    (0..20_i64)
+        .prop_map(SNum::Lit)
+        .prop_recursive(6, 64, 2, |inner| {
+            (inner.clone(), inner).prop_map(|(left, right)| SNum::Pair(Box::new([left, right])))
+        })
+}
+
+/// Packets the parser accepts: literals of at most 16 groups, minimums and maximums of at
+/// least one packet, comparisons of exactly two.
+fn packet() -> impl Strategy<Value = Packet> {
+    use OperatorType::*;
+
+    let literal = (0..8_u8, prop::collection::vec(0..16_u8, 1..=16))
+        .prop_map(|(version, parts)| Packet::Literal { version, parts });
+
+    literal.prop_recursive(4, 48, 4, |inner| {
+        let operator = |typ, count| {
+            (0..8_u8, prop::collection::vec(inner.clone(), count)).prop_map(
+                move |(version, subpackets)| Packet::Operator {
+                    version,
+                    typ,
+                    subpackets,
+                },
+            )
+        };
+        prop_oneof![
+            operator(Sum, 0..4),
+            operator(Product, 0..4),
+            operator(Minimum, 1..4),
+            operator(Maximum, 1..4),
+            operator(GreaterThan, 2..3),
+            operator(LessThan, 2..3),
+            operator(EqualTo, 2..3),
+        ]
+    })
+}
+
+fn cuboid() -> impl Strategy<Value = Cuboid> {
+    let range = || (-20..20_i64, 0..15_i64).prop_map(|(start, len)| start..=start + len);
+    (range(), range(), range()).prop_map(|(x, y, z)| Cuboid::from_ranges(x, y, z))
+}
+
+fn vec3() -> impl Strategy<Value = Vec3> {
+    (-1000..1000_i64, -1000..1000_i64, -1000..1000_i64).prop_map(|(x, y, z)| Vec3::new(x, y, z))
+}
+
+fn orientation() -> impl Strategy<Value = Mat3> {
+    (0..24_usize).prop_map(|i| Mat3::orientations()[i])
+}
+
+proptest! {
+    #[test]
+    fn day18_display_parses_back(n in snum()) {
+        let printed = n.to_string();
+        let parsed: SNum = printed.parse().unwrap();
+        prop_assert_eq!(&parsed, &n);
+        prop_assert_eq!(parsed.magnitude(), n.magnitude());
+
+        // and in the puzzle's own format, one number per line
+        let compact = day18::parse(&printed.replace(' ', "")).unwrap();
+        prop_assert_eq!(&compact, &[n]);
+    }
+
+    #[test]
+    fn day16_hex_parses_back(p in packet()) {
+        let hex = p.to_hex().unwrap();
+        prop_assert_eq!(day16::parse(&hex).unwrap(), p);
+    }
+
+    #[test]
+    fn day22_cuboid_pieces_add_up(a in cuboid(), b in cuboid()) {
+        let mut pieces: Vec<Cuboid> = a.difference(&b).collect();
+        for piece in &pieces {
+            prop_assert_eq!(piece.intersection(&b), None);
+        }
+        pieces.extend(a.intersection(&b));
+
+        for (i, piece) in pieces.iter().enumerate() {
+            prop_assert_eq!(piece.intersection(&a), Some(*piece));
+            for other in &pieces[..i] {
+                prop_assert_eq!(piece.intersection(other), None);
+            }
+        }
+        prop_assert_eq!(pieces.iter().map(Cuboid::volume).sum::<u128>(), a.volume());
+    }
+
+    #[test]
+    fn day19_rotations_compose(r in orientation(), s in orientation(), v in vec3(), w in vec3()) {
+        let rs = r * s;
+        prop_assert!(Mat3::orientations().contains(&rs));
+        prop_assert_eq!(rs * v, r * (s * v));
+        prop_assert_eq!(rs.inverse(), Some(s.inverse().unwrap() * r.inverse().unwrap()));
+        prop_assert_eq!(r.inverse().unwrap() * (r * v), v);
+
+        // rotations move points, not the distances between them
+        prop_assert_eq!(manhattan(r * v, r * w), manhattan(v, w));
+        prop_assert_eq!(euclidean(r * v, r * w), euclidean(v, w));
+    }
+}